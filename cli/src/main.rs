@@ -0,0 +1,601 @@
+//! Command line tool for the Blockchain Authenticator contract :
+//!
+//! - encode : write the file of "casper-client put-deploy --session-args-complex" for a method
+//! - decode : print the IDs of a list from a JSON dump of its named key or of its dictionary items
+//! - diff : compare the IDs of two dumps
+//! - export : print the otpauth URIs of a dump, to move the secrets into another authenticator app
+
+mod snapshot;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use blockchain_authenticator_client::{
+    AuthenticatorArgs, DeviceScope, Method, Operation, Role, SecretEntry,
+};
+use casper_types::{account::AccountHash, PublicKey};
+use clap::{ArgEnum, Args, Parser, Subcommand};
+
+use snapshot::{Snapshot, SnapshotError};
+
+#[derive(Parser)]
+#[clap(name = "blockchain-authenticator", version, about)]
+struct Cli {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the session args file of casper-client for a method
+    Encode {
+        /// Version of the list read before the call, the call is reverted if the list was modified since
+        #[clap(long)]
+        expected_version: Option<u64>,
+        #[clap(subcommand)]
+        method: EncodeMethod,
+    },
+    /// Print the IDs of a list from a JSON dump of a StoredValue or a CLValue, or from the dumps of
+    /// the items of its dictionaries
+    Decode {
+        /// JSON dump, as printed by "casper-client query-global-state" or
+        /// "casper-client get-dictionary-item", or {"meta": {...}, "elements": {...}}
+        snapshot: PathBuf,
+    },
+    /// Print the IDs added (+), removed (-) and modified (~) between two JSON dumps
+    Diff { old: PathBuf, new: PathBuf },
+    /// Print the otpauth URI of each element of a JSON dump. The encrypted elements are skipped.
+    Export { snapshot: PathBuf },
+}
+
+/// Elements of "add", "insert" and "update"
+#[derive(Args)]
+struct WriteArgs {
+    #[clap(long)]
+    named_key: String,
+    #[clap(required_unless_present = "uris")]
+    entries: Vec<String>,
+    /// otpauth URI of an element, its label becomes the ID (can be repeated)
+    #[clap(long = "uri")]
+    uris: Vec<String>,
+}
+
+impl WriteArgs {
+    fn into_args(self, method: Method) -> AuthenticatorArgs {
+        let mut args = AuthenticatorArgs::new(method, &self.named_key);
+        if !self.entries.is_empty() {
+            args = args.with_entries(&self.entries);
+        }
+        if !self.uris.is_empty() {
+            args = args.with_uris(&self.uris);
+        }
+        args
+    }
+}
+
+/// Role of "grant"
+#[derive(Clone, Copy, ArgEnum)]
+enum RoleArg {
+    Reader,
+    Writer,
+    Owner,
+}
+
+impl From<RoleArg> for Role {
+    fn from(role: RoleArg) -> Self {
+        match role {
+            RoleArg::Reader => Role::Reader,
+            RoleArg::Writer => Role::Writer,
+            RoleArg::Owner => Role::Owner,
+        }
+    }
+}
+
+/// Scope of "adddevice"
+#[derive(Clone, Copy, ArgEnum)]
+enum ScopeArg {
+    ReadOnly,
+    AddOnly,
+    Full,
+}
+
+impl From<ScopeArg> for DeviceScope {
+    fn from(scope: ScopeArg) -> Self {
+        match scope {
+            ScopeArg::ReadOnly => DeviceScope::ReadOnly,
+            ScopeArg::AddOnly => DeviceScope::AddOnly,
+            ScopeArg::Full => DeviceScope::Full,
+        }
+    }
+}
+
+fn parse_public_key(public_key: &str) -> Result<PublicKey, String> {
+    PublicKey::from_hex(public_key)
+        .map_err(|_| String::from("expected the hex of a public key, as printed by casper-client"))
+}
+
+fn parse_account(account: &str) -> Result<AccountHash, String> {
+    AccountHash::from_formatted_str(account)
+        .map_err(|_| String::from("expected \"account-hash-[64 hex characters]\""))
+}
+
+/// Operation of "batch" : "add:[ID];[VALUE]", "upsert:[ID];[VALUE]", "delete:[ID]",
+/// "rename:[ID];[NEW ID]" or "move:[ID];[NAMED KEY]". An ID never contains ";".
+/// An encrypted element is renamed with its secret sealed with the new ID : "rename:[ID];[NEW ID];[ENVELOPE]".
+fn parse_operation(operation: &str) -> Result<Operation, String> {
+    let expected = || {
+        format!(
+            "expected \"add:[ID];[VALUE]\", \"upsert:[ID];[VALUE]\", \"delete:[ID]\", \"rename:[ID];[NEW ID][;ENVELOPE]\" or \"move:[ID];[NAMED KEY]\", got \"{}\"",
+            operation
+        )
+    };
+    let (kind, value) = operation.split_once(':').ok_or_else(expected)?;
+    let split = || value.split_once(';').ok_or_else(expected);
+    match kind {
+        "add" => SecretEntry::from_legacy(value)
+            .map(Operation::Add)
+            .ok_or_else(expected),
+        "upsert" => SecretEntry::from_legacy(value)
+            .map(Operation::Upsert)
+            .ok_or_else(expected),
+        "delete" => Ok(Operation::Delete(String::from(value))),
+        "rename" => split().map(|(id, new_id)| {
+            let (new_id, secret) = match new_id.split_once(';') {
+                Some((new_id, secret)) => (new_id, Some(String::from(secret))),
+                None => (new_id, None),
+            };
+            Operation::RenameId {
+                id: String::from(id),
+                new_id: String::from(new_id),
+                secret,
+            }
+        }),
+        "move" => split().map(|(id, named_key)| Operation::Move {
+            id: String::from(id),
+            named_key: String::from(named_key),
+        }),
+        _ => Err(expected()),
+    }
+}
+
+#[derive(Subcommand)]
+enum EncodeMethod {
+    /// Add or update elements "[ID];[VALUE]"
+    Add(WriteArgs),
+    /// Add elements "[ID];[VALUE]", the call fails if an ID already exists
+    Insert(WriteArgs),
+    /// Update elements "[ID];[VALUE]", the call fails if an ID doesn't exist
+    Update(WriteArgs),
+    /// Move elements to the trash, given as "[ID]" or "[ID];[VALUE]"
+    Del {
+        #[clap(long)]
+        named_key: String,
+        #[clap(required = true)]
+        entries: Vec<String>,
+        /// Only the elements carrying the tag, or in the folder of the same name
+        #[clap(long)]
+        tag: Option<String>,
+    },
+    /// Move all the elements to the trash
+    Delall {
+        #[clap(long)]
+        named_key: String,
+        /// Erase the elements instead of moving them to the trash
+        #[clap(long)]
+        force: bool,
+        /// Also erase the IDs from the index and empty the trash
+        #[clap(long)]
+        purge: bool,
+        /// Only the elements carrying the tag, or in the folder of the same name. They are always
+        /// moved to the trash
+        #[clap(long, conflicts_with_all = &["force", "purge"])]
+        tag: Option<String>,
+    },
+    /// Apply several operations to a list in a single deploy, in their order
+    Batch {
+        #[clap(long)]
+        named_key: String,
+        /// "add:[ID];[VALUE]", "upsert:[ID];[VALUE]", "delete:[ID]", "rename:[ID];[NEW ID][;ENVELOPE]"
+        /// or "move:[ID];[NAMED KEY]"
+        #[clap(required = true, parse(try_from_str = parse_operation))]
+        operations: Vec<Operation>,
+    },
+    /// Move elements back from the trash, given as "[ID]" or "[ID];[VALUE]"
+    Restore {
+        #[clap(long)]
+        named_key: String,
+        #[clap(required = true)]
+        entries: Vec<String>,
+    },
+    /// Erase trashed elements whose retention period is over (all of them if no ID is given)
+    Purge {
+        #[clap(long)]
+        named_key: String,
+        entries: Vec<String>,
+    },
+    /// Convert a list created by a previous version of the contract
+    Migrate {
+        #[clap(long)]
+        named_key: String,
+    },
+    /// Return the elements of a list
+    Get {
+        #[clap(long)]
+        named_key: String,
+    },
+    /// Return the names of the lists
+    Lists,
+    /// Change the settings of a list, a setting that is not given is not modified
+    Configure {
+        #[clap(long)]
+        named_key: String,
+        /// true to only accept encrypted elements
+        #[clap(long)]
+        strict: Option<bool>,
+        /// Time in milliseconds before a trashed element can be purged
+        #[clap(long)]
+        retention: Option<u64>,
+    },
+    /// Move the counter of a counter-based element to the next password
+    Increment {
+        #[clap(long)]
+        named_key: String,
+        id: String,
+    },
+    /// Move the counter of a counter-based element forward to the counter of a device
+    Resync {
+        #[clap(long)]
+        named_key: String,
+        id: String,
+        counter: u64,
+    },
+    /// Put the elements in a new order, given as the IDs of all the elements
+    Reorder {
+        #[clap(long)]
+        named_key: String,
+        #[clap(required = true)]
+        ids: Vec<String>,
+    },
+    /// Pin an element, so it is shown before the others
+    Pin {
+        #[clap(long)]
+        named_key: String,
+        id: String,
+        /// Unpin the element instead
+        #[clap(long)]
+        unpin: bool,
+    },
+    /// Move a list to a new named key
+    Rename {
+        #[clap(long)]
+        named_key: String,
+        new_named_key: String,
+    },
+    /// Copy the elements of a list into another list
+    Copy {
+        #[clap(long)]
+        named_key: String,
+        new_named_key: String,
+    },
+    /// Remove a list and its named keys
+    Dellist {
+        #[clap(long)]
+        named_key: String,
+    },
+    /// Give a role on a shared list to an account, with the "shared" entry point
+    Grant {
+        #[clap(long)]
+        named_key: String,
+        /// Account, as "account-hash-[hex]"
+        #[clap(parse(try_from_str = parse_account))]
+        account: AccountHash,
+        #[clap(arg_enum)]
+        role: RoleArg,
+    },
+    /// Remove the role of an account on a shared list, with the "shared" entry point
+    Revoke {
+        #[clap(long)]
+        named_key: String,
+        /// Account, as "account-hash-[hex]"
+        #[clap(parse(try_from_str = parse_account))]
+        account: AccountHash,
+    },
+    /// Allow a key to call a list, the deploys must then be signed by one of the devices
+    Adddevice {
+        #[clap(long)]
+        named_key: String,
+        /// Public key of the device, in hex
+        #[clap(parse(try_from_str = parse_public_key))]
+        device: PublicKey,
+        label: String,
+        #[clap(arg_enum)]
+        scope: ScopeArg,
+    },
+    /// Remove a key from the devices of a list
+    Deldevice {
+        #[clap(long)]
+        named_key: String,
+        /// Public key of the device, in hex
+        #[clap(parse(try_from_str = parse_public_key))]
+        device: PublicKey,
+    },
+    /// Choose the guardians who can give a shared list to a new owner, with the "shared" entry point
+    Setguardians {
+        #[clap(long)]
+        named_key: String,
+        /// Number of guardians who must approve the new owner
+        #[clap(long)]
+        threshold: u32,
+        /// Time in milliseconds between the approval and the recovery
+        #[clap(long)]
+        timelock: u64,
+        /// Accounts of the guardians, as "account-hash-[hex]"
+        #[clap(parse(try_from_str = parse_account), required = true)]
+        guardians: Vec<AccountHash>,
+    },
+    /// Approve, as a guardian, giving a shared list to a new owner
+    Approverecovery {
+        #[clap(long)]
+        named_key: String,
+        /// Account of the new owner, as "account-hash-[hex]"
+        #[clap(parse(try_from_str = parse_account))]
+        new_owner: AccountHash,
+    },
+    /// Cancel the pending recovery of a shared list
+    Cancelrecovery {
+        #[clap(long)]
+        named_key: String,
+    },
+    /// Give a shared list to the approved owner once the timelock is over
+    Completerecovery {
+        #[clap(long)]
+        named_key: String,
+    },
+    /// Choose the account that can claim a shared list when its owner is inactive
+    Setbeneficiary {
+        #[clap(long)]
+        named_key: String,
+        /// Time in milliseconds without "add", "del" or "heartbeat" of the owner before the claim
+        #[clap(long)]
+        inactivity: u64,
+        /// Account of the beneficiary, as "account-hash-[hex]"
+        #[clap(parse(try_from_str = parse_account))]
+        beneficiary: AccountHash,
+        /// Role given to the beneficiary by the claim
+        #[clap(arg_enum)]
+        role: RoleArg,
+    },
+    /// Tell the contract that the owner of a shared list is still active
+    Heartbeat {
+        #[clap(long)]
+        named_key: String,
+    },
+    /// Claim a shared list, as its beneficiary, once the owner is inactive
+    Claim {
+        #[clap(long)]
+        named_key: String,
+    },
+}
+
+fn main() {
+    let cli = Cli::parse();
+    if let Err(error) = run(cli.command) {
+        eprintln!("error: {}", error);
+        process::exit(1);
+    }
+}
+
+fn run(command: Command) -> Result<(), SnapshotError> {
+    match command {
+        Command::Encode {
+            expected_version,
+            method,
+        } => {
+            let mut args = match method {
+                EncodeMethod::Add(write_args) => write_args.into_args(Method::Add),
+                EncodeMethod::Insert(write_args) => write_args.into_args(Method::Insert),
+                EncodeMethod::Update(write_args) => write_args.into_args(Method::Update),
+                EncodeMethod::Del {
+                    named_key,
+                    entries,
+                    tag,
+                } => {
+                    let args = AuthenticatorArgs::del(&named_key, &entries);
+                    match tag {
+                        Some(tag) => args.with_tag(&tag),
+                        None => args,
+                    }
+                }
+                EncodeMethod::Delall {
+                    named_key,
+                    force,
+                    purge,
+                    tag,
+                } => match tag {
+                    Some(tag) => AuthenticatorArgs::delall(&named_key).with_tag(&tag),
+                    None => AuthenticatorArgs::delall(&named_key)
+                        .with_force(force)
+                        .with_purge(purge),
+                },
+                EncodeMethod::Batch {
+                    named_key,
+                    operations,
+                } => AuthenticatorArgs::batch(&named_key, &operations),
+                EncodeMethod::Restore { named_key, entries } => {
+                    AuthenticatorArgs::restore(&named_key, &entries)
+                }
+                EncodeMethod::Purge { named_key, entries } => {
+                    let args = AuthenticatorArgs::purge(&named_key);
+                    if entries.is_empty() {
+                        args
+                    } else {
+                        args.with_entries(&entries)
+                    }
+                }
+                EncodeMethod::Migrate { named_key } => AuthenticatorArgs::migrate(&named_key),
+                EncodeMethod::Get { named_key } => AuthenticatorArgs::get(&named_key),
+                EncodeMethod::Lists => AuthenticatorArgs::lists(),
+                EncodeMethod::Configure {
+                    named_key,
+                    strict,
+                    retention,
+                } => {
+                    let mut args = AuthenticatorArgs::configure(&named_key);
+                    if let Some(strict) = strict {
+                        args = args.with_strict(strict);
+                    }
+                    if let Some(retention) = retention {
+                        args = args.with_retention(retention);
+                    }
+                    args
+                }
+                EncodeMethod::Increment { named_key, id } => {
+                    AuthenticatorArgs::increment(&named_key, &id)
+                }
+                EncodeMethod::Resync {
+                    named_key,
+                    id,
+                    counter,
+                } => AuthenticatorArgs::resync(&named_key, &id, counter),
+                EncodeMethod::Reorder { named_key, ids } => {
+                    AuthenticatorArgs::reorder(&named_key, &ids)
+                }
+                EncodeMethod::Pin {
+                    named_key,
+                    id,
+                    unpin,
+                } => AuthenticatorArgs::pin(&named_key, &id, !unpin),
+                EncodeMethod::Rename {
+                    named_key,
+                    new_named_key,
+                } => AuthenticatorArgs::rename(&named_key, &new_named_key),
+                EncodeMethod::Copy {
+                    named_key,
+                    new_named_key,
+                } => AuthenticatorArgs::copy(&named_key, &new_named_key),
+                EncodeMethod::Dellist { named_key } => AuthenticatorArgs::dellist(&named_key),
+                EncodeMethod::Grant {
+                    named_key,
+                    account,
+                    role,
+                } => AuthenticatorArgs::grant(&named_key, account, role.into()),
+                EncodeMethod::Revoke { named_key, account } => {
+                    AuthenticatorArgs::revoke(&named_key, account)
+                }
+                EncodeMethod::Adddevice {
+                    named_key,
+                    device,
+                    label,
+                    scope,
+                } => AuthenticatorArgs::add_device(&named_key, device, &label, scope.into()),
+                EncodeMethod::Deldevice { named_key, device } => {
+                    AuthenticatorArgs::del_device(&named_key, device)
+                }
+                EncodeMethod::Setguardians {
+                    named_key,
+                    threshold,
+                    timelock,
+                    guardians,
+                } => AuthenticatorArgs::set_guardians(&named_key, &guardians, threshold, timelock),
+                EncodeMethod::Approverecovery {
+                    named_key,
+                    new_owner,
+                } => AuthenticatorArgs::approve_recovery(&named_key, new_owner),
+                EncodeMethod::Cancelrecovery { named_key } => {
+                    AuthenticatorArgs::cancel_recovery(&named_key)
+                }
+                EncodeMethod::Completerecovery { named_key } => {
+                    AuthenticatorArgs::complete_recovery(&named_key)
+                }
+                EncodeMethod::Setbeneficiary {
+                    named_key,
+                    inactivity,
+                    beneficiary,
+                    role,
+                } => AuthenticatorArgs::set_beneficiary(
+                    &named_key,
+                    beneficiary,
+                    role.into(),
+                    inactivity,
+                ),
+                EncodeMethod::Heartbeat { named_key } => AuthenticatorArgs::heartbeat(&named_key),
+                EncodeMethod::Claim { named_key } => AuthenticatorArgs::claim(&named_key),
+            };
+            if let Some(expected_version) = expected_version {
+                args = args.with_expected_version(expected_version);
+            }
+            println!("{}", args.to_json());
+        }
+        Command::Decode { snapshot } => {
+            print!("{}", read_snapshot(&snapshot)?.table());
+        }
+        Command::Diff { old, new } => {
+            print!("{}", read_snapshot(&old)?.diff(&read_snapshot(&new)?));
+        }
+        Command::Export { snapshot } => {
+            for (id, uri) in read_snapshot(&snapshot)?.uris() {
+                match uri {
+                    Some(uri) => println!("{}", uri),
+                    None => eprintln!("skipped {} : the secret is encrypted", id),
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn read_snapshot(path: &Path) -> Result<Snapshot, SnapshotError> {
+    let json = fs::read_to_string(path)
+        .map_err(|error| SnapshotError::Io(path.display().to_string(), error))?;
+    Snapshot::from_json(&json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_operations() {
+        assert_eq!(
+            parse_operation("add:ID1;VALUE1"),
+            Ok(Operation::Add(SecretEntry::new("ID1", "VALUE1")))
+        );
+        assert_eq!(
+            parse_operation("upsert:ID1;v1;000000000000000000000000;00"),
+            Ok(Operation::Upsert(SecretEntry::new(
+                "ID1",
+                "v1;000000000000000000000000;00"
+            )))
+        );
+        assert_eq!(
+            parse_operation("delete:ID1"),
+            Ok(Operation::Delete(String::from("ID1")))
+        );
+        assert_eq!(
+            parse_operation("rename:Example:alice;Example:bob"),
+            Ok(Operation::RenameId {
+                id: String::from("Example:alice"),
+                new_id: String::from("Example:bob"),
+                secret: None,
+            })
+        );
+        assert_eq!(
+            parse_operation("rename:ID1;ID2;v1;000000000000000000000000;00"),
+            Ok(Operation::RenameId {
+                id: String::from("ID1"),
+                new_id: String::from("ID2"),
+                secret: Some(String::from("v1;000000000000000000000000;00")),
+            })
+        );
+        assert_eq!(
+            parse_operation("move:ID1;other-list"),
+            Ok(Operation::Move {
+                id: String::from("ID1"),
+                named_key: String::from("other-list"),
+            })
+        );
+        assert!(parse_operation("add:ID1").is_err());
+        assert!(parse_operation("erase:ID1").is_err());
+    }
+}
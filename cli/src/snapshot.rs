@@ -0,0 +1,432 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter};
+use std::io;
+
+use blockchain_authenticator_client::SecretEntry;
+use blockchain_authenticator_envelope::Envelope;
+use casper_types::bytesrepr;
+use serde_json::{json, Map, Value};
+
+/// Member of a dump of dictionary items holding the items of "[named-key]_meta"
+const META_MEMBER: &str = "meta";
+/// Member of a dump of dictionary items holding the items of "[named-key]"
+const ELEMENTS_MEMBER: &str = "elements";
+/// Prefix of the items of "[named-key]_meta" linking the IDs of the list
+const NODE_PREFIX: &str = "ids:node:";
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// The file can't be read
+    Io(String, io::Error),
+    /// The file is not JSON
+    Json(serde_json::Error),
+    /// The JSON doesn't hold a CLValue
+    NotACLValue,
+    /// The CLValue is neither a list nor an element
+    UnsupportedType(Value),
+    /// The bytes of the CLValue can't be decoded
+    InvalidBytes,
+    /// An ID of the index has no element in the dump
+    MissingElement(String),
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SnapshotError::Io(path, error) => write!(f, "can't read {} : {}", path, error),
+            SnapshotError::Json(error) => write!(f, "invalid JSON : {}", error),
+            SnapshotError::NotACLValue => write!(f, "no CLValue in the JSON"),
+            SnapshotError::UnsupportedType(cl_type) => {
+                write!(f, "expected a list or an element, found {}", cl_type)
+            }
+            SnapshotError::InvalidBytes => write!(f, "the bytes don't match the type"),
+            SnapshotError::MissingElement(id) => write!(f, "no element for the ID {}", id),
+        }
+    }
+}
+
+/// Elements of a list, read from a JSON dump of a named key (a list created by a previous version
+/// of the contract), of an element of a dictionary, of the result of "get", or of the items of the
+/// dictionaries of a list.
+/// A "[ID];[VALUE]" string is read as `SecretEntry::new(ID, VALUE)`.
+#[derive(Debug, PartialEq)]
+pub struct Snapshot {
+    entries: Vec<SecretEntry>,
+}
+
+impl Snapshot {
+    /// Decode a CLValue, a StoredValue holding a CLValue, the response of
+    /// "casper-client query-global-state" or "casper-client get-dictionary-item", or the items of
+    /// the dictionaries of a list (see `from_items`).
+    pub fn from_json(json: &str) -> Result<Self, SnapshotError> {
+        let json: Value = serde_json::from_str(json).map_err(SnapshotError::Json)?;
+        if let Some(elements) = json.get(ELEMENTS_MEMBER).and_then(Value::as_object) {
+            return Snapshot::from_items(
+                json.get(META_MEMBER).and_then(Value::as_object),
+                elements,
+            );
+        }
+        Ok(Snapshot {
+            entries: decode_entries(&json)?,
+        })
+    }
+
+    /// Decode the items of the dictionaries of a list, each dumped by
+    /// "casper-client get-dictionary-item" :
+    /// {"meta": {"ids:node:[ID]": dump, ...}, "elements": {"[ID]": dump, ...}}.
+    /// The IDs of the list are the nodes of the index that are not None, the other elements were
+    /// removed or trashed. Without "meta", the elements that are not None are read.
+    fn from_items(
+        meta: Option<&Map<String, Value>>,
+        elements: &Map<String, Value>,
+    ) -> Result<Self, SnapshotError> {
+        let mut entries = Vec::new();
+        for dump in elements.values() {
+            entries.extend(decode_entries(dump)?);
+        }
+        if let Some(meta) = meta {
+            let mut ids: BTreeSet<&str> = BTreeSet::new();
+            for (key, dump) in meta.iter() {
+                if let Some(id) = key.strip_prefix(NODE_PREFIX) {
+                    if decode_node(dump)?.is_some() {
+                        ids.insert(id);
+                    }
+                }
+            }
+            if let Some(id) = ids
+                .iter()
+                .find(|id| !entries.iter().any(|entry| entry.id == **id))
+            {
+                return Err(SnapshotError::MissingElement(id.to_string()));
+            }
+            entries.retain(|entry| ids.contains(entry.id.as_str()));
+        }
+        Ok(Snapshot { entries })
+    }
+
+    /// The element of each ID. A list created by a previous version of the contract can hold the
+    /// same ID several times, the most recent element wins.
+    fn by_id(&self) -> BTreeMap<&str, &SecretEntry> {
+        self.entries
+            .iter()
+            .map(|entry| (entry.id.as_str(), entry))
+            .collect()
+    }
+
+    /// The IDs sorted, and whether their secret is encrypted. The secrets are never printed.
+    pub fn table(&self) -> String {
+        let by_id = self.by_id();
+        let width = by_id.keys().map(|id| id.len()).max().unwrap_or(0).max(2);
+
+        let mut table = format!("{:width$}  ENCRYPTED\n", "ID", width = width);
+        for (id, entry) in by_id {
+            let encrypted = if is_encrypted(entry) { "yes" } else { "no" };
+            table.push_str(&format!("{:width$}  {}\n", id, encrypted, width = width));
+        }
+        table
+    }
+
+    /// The otpauth URI of each element, sorted by ID. None for an encrypted element, whose secret
+    /// must be opened before it can be exported.
+    pub fn uris(&self) -> Vec<(&str, Option<String>)> {
+        self.by_id()
+            .into_iter()
+            .map(|(id, entry)| {
+                let uri = if is_encrypted(entry) {
+                    None
+                } else {
+                    Some(entry.to_otpauth_uri())
+                };
+                (id, uri)
+            })
+            .collect()
+    }
+
+    /// The IDs added (+), removed (-) or modified (~) in a newer snapshot, sorted.
+    pub fn diff(&self, new: &Snapshot) -> String {
+        let old_by_id = self.by_id();
+        let new_by_id = new.by_id();
+
+        let mut changes: BTreeMap<&str, char> = BTreeMap::new();
+        for (id, value) in new_by_id.iter() {
+            match old_by_id.get(id) {
+                None => changes.insert(*id, '+'),
+                Some(old_value) if old_value != value => changes.insert(*id, '~'),
+                Some(_) => None,
+            };
+        }
+        for id in old_by_id.keys().filter(|id| !new_by_id.contains_key(*id)) {
+            changes.insert(*id, '-');
+        }
+
+        changes
+            .iter()
+            .map(|(id, change)| format!("{} {}\n", change, id))
+            .collect()
+    }
+}
+
+/// Convert a "[ID];[VALUE]" string. A removed element (empty string) is ignored.
+fn legacy_entry(entry: &str) -> Option<SecretEntry> {
+    if entry.is_empty() {
+        return None;
+    }
+    SecretEntry::from_legacy(entry).or_else(|| Some(SecretEntry::new(entry, "")))
+}
+
+fn is_encrypted(entry: &SecretEntry) -> bool {
+    Envelope::parse(&entry.secret).is_ok()
+}
+
+/// The elements of a CLValue : a list or an element created by a previous version of the
+/// contract, the result of "get", or an item of the dictionary of the elements.
+fn decode_entries(json: &Value) -> Result<Vec<SecretEntry>, SnapshotError> {
+    let (cl_type, bytes) = cl_value(json)?;
+    let entries = if *cl_type == json!({ "List": "String" }) {
+        bytesrepr::deserialize::<Vec<String>>(bytes).map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| legacy_entry(entry))
+                .collect()
+        })
+    } else if *cl_type == json!("String") {
+        bytesrepr::deserialize::<String>(bytes)
+            .map(|entry| legacy_entry(&entry).into_iter().collect())
+    } else if *cl_type == json!({ "List": "Any" }) {
+        bytesrepr::deserialize::<Vec<SecretEntry>>(bytes)
+    } else if *cl_type == json!({ "Option": "Any" }) {
+        bytesrepr::deserialize::<Option<SecretEntry>>(bytes)
+            .map(|entry| entry.into_iter().collect())
+    } else {
+        return Err(SnapshotError::UnsupportedType(cl_type.clone()));
+    };
+    entries.map_err(|_| SnapshotError::InvalidBytes)
+}
+
+/// The previous and the next IDs of a node of the index, None once the ID was removed.
+fn decode_node(json: &Value) -> Result<Option<(String, String)>, SnapshotError> {
+    let (cl_type, bytes) = cl_value(json)?;
+    if *cl_type != json!({ "Option": { "Tuple2": ["String", "String"] } }) {
+        return Err(SnapshotError::UnsupportedType(cl_type.clone()));
+    }
+    bytesrepr::deserialize(bytes).map_err(|_| SnapshotError::InvalidBytes)
+}
+
+/// The type and the bytes of the CLValue of a dump.
+fn cl_value(json: &Value) -> Result<(&Value, Vec<u8>), SnapshotError> {
+    let cl_value = find_cl_value(json).ok_or(SnapshotError::NotACLValue)?;
+    let bytes = cl_value["bytes"]
+        .as_str()
+        .and_then(|bytes| hex::decode(bytes).ok())
+        .ok_or(SnapshotError::InvalidBytes)?;
+    Ok((&cl_value["cl_type"], bytes))
+}
+
+/// Look for the object {"cl_type", "bytes"} of a CLValue, inside a StoredValue {"CLValue"} or
+/// inside an RPC response {"result": {"stored_value"}}.
+fn find_cl_value(json: &Value) -> Option<&Value> {
+    if json.get("cl_type").is_some() && json.get("bytes").is_some() {
+        return Some(json);
+    }
+    ["result", "stored_value", "CLValue"]
+        .iter()
+        .find_map(|key| json.get(key))
+        .and_then(find_cl_value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use casper_types::bytesrepr::ToBytes;
+
+    fn stored_value_json(entries: &[&str]) -> String {
+        let entries: Vec<String> = entries.iter().map(|entry| entry.to_string()).collect();
+        json!({
+            "CLValue": {
+                "cl_type": { "List": "String" },
+                "bytes": hex::encode(entries.to_bytes().unwrap()),
+                "parsed": entries
+            }
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn should_decode_stored_value() {
+        let snapshot = Snapshot::from_json(&stored_value_json(&[
+            "ID1;VALUE",
+            "ID10;v1;000000000000000000000000;00000000000000000000000000000000",
+        ]))
+        .unwrap();
+
+        assert_eq!(snapshot.table(), "ID    ENCRYPTED\nID1   no\nID10  yes\n");
+    }
+
+    #[test]
+    fn should_decode_query_response() {
+        let json = json!({
+            "jsonrpc": "2.0",
+            "result": {
+                "stored_value": {
+                    "CLValue": {
+                        "cl_type": "String",
+                        "bytes": "090000004944313b56414c5545",
+                        "parsed": "ID1;VALUE"
+                    }
+                }
+            }
+        });
+
+        assert_eq!(
+            Snapshot::from_json(&json.to_string()).unwrap(),
+            Snapshot {
+                entries: vec![SecretEntry::new("ID1", "VALUE")]
+            }
+        );
+    }
+
+    #[test]
+    fn should_decode_secret_entries() {
+        let entry = SecretEntry::new("ID1", "VALUE");
+        let json = json!({
+            "CLValue": {
+                "cl_type": { "Option": "Any" },
+                "bytes": hex::encode(Some(entry).to_bytes().unwrap()),
+                "parsed": null
+            }
+        });
+
+        assert_eq!(
+            Snapshot::from_json(&json.to_string()).unwrap(),
+            Snapshot {
+                entries: vec![SecretEntry::new("ID1", "VALUE")]
+            }
+        );
+    }
+
+    #[test]
+    fn should_diff_snapshots() {
+        let old = Snapshot::from_json(&stored_value_json(&["ID1;A", "ID2;B", "ID3;C"])).unwrap();
+        let new = Snapshot::from_json(&stored_value_json(&["ID2;B", "ID3;D", "ID4;E"])).unwrap();
+
+        assert_eq!(old.diff(&new), "- ID1\n~ ID3\n+ ID4\n");
+    }
+
+    fn element_json(entry: &SecretEntry) -> Value {
+        json!({
+            "CLValue": {
+                "cl_type": { "Option": "Any" },
+                "bytes": hex::encode(Some(entry.clone()).to_bytes().unwrap()),
+                "parsed": null
+            }
+        })
+    }
+
+    fn node_json(node: Option<(&str, &str)>) -> Value {
+        let node = node.map(|(previous, next)| (previous.to_string(), next.to_string()));
+        json!({
+            "CLValue": {
+                "cl_type": { "Option": { "Tuple2": ["String", "String"] } },
+                "bytes": hex::encode(node.to_bytes().unwrap()),
+                "parsed": null
+            }
+        })
+    }
+
+    #[test]
+    fn should_decode_items_of_list() {
+        let json = json!({
+            "meta": {
+                "ids:length": {
+                    "CLValue": { "cl_type": "U64", "bytes": "0200000000000000", "parsed": 2 }
+                },
+                "ids:node:ID1": node_json(Some(("", "ID2"))),
+                "ids:node:ID2": node_json(Some(("ID1", ""))),
+                "ids:node:ID3": node_json(None)
+            },
+            "elements": {
+                "ID1": element_json(&SecretEntry::new("ID1", "VALUE1")),
+                "ID2": element_json(&SecretEntry::new("ID2", "VALUE2")),
+                "ID3": element_json(&SecretEntry::new("ID3", "VALUE3"))
+            }
+        });
+
+        assert_eq!(
+            Snapshot::from_json(&json.to_string()).unwrap(),
+            Snapshot {
+                entries: vec![
+                    SecretEntry::new("ID1", "VALUE1"),
+                    SecretEntry::new("ID2", "VALUE2")
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn should_reject_id_without_element() {
+        let json = json!({
+            "meta": { "ids:node:ID1": node_json(Some(("", ""))) },
+            "elements": {}
+        });
+
+        assert!(matches!(
+            Snapshot::from_json(&json.to_string()),
+            Err(SnapshotError::MissingElement(id)) if id == "ID1"
+        ));
+    }
+
+    #[test]
+    fn should_diff_items_of_list() {
+        let old = json!({
+            "elements": {
+                "ID1": element_json(&SecretEntry::new("ID1", "A")),
+                "ID2": element_json(&SecretEntry::new("ID2", "B"))
+            }
+        });
+        let new = json!({
+            "elements": {
+                "ID2": element_json(&SecretEntry::new("ID2", "C")),
+                "ID3": element_json(&SecretEntry::new("ID3", "D"))
+            }
+        });
+
+        assert_eq!(
+            Snapshot::from_json(&old.to_string())
+                .unwrap()
+                .diff(&Snapshot::from_json(&new.to_string()).unwrap()),
+            "- ID1\n~ ID2\n+ ID3\n"
+        );
+    }
+
+    #[test]
+    fn should_export_plaintext_entries() {
+        let snapshot = Snapshot::from_json(&stored_value_json(&[
+            "ID1;JBSWY3DPEHPK3PXP",
+            "ID2;v1;000000000000000000000000;00000000000000000000000000000000",
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            snapshot.uris(),
+            vec![
+                (
+                    "ID1",
+                    Some(String::from(
+                        "otpauth://totp/ID1?secret=JBSWY3DPEHPK3PXP&algorithm=SHA1&digits=6&period=30"
+                    ))
+                ),
+                ("ID2", None)
+            ]
+        );
+    }
+
+    #[test]
+    fn should_reject_other_types() {
+        let json = json!({ "CLValue": { "cl_type": "U64", "bytes": "0000000000000000" } });
+
+        assert!(matches!(
+            Snapshot::from_json(&json.to_string()),
+            Err(SnapshotError::UnsupportedType(_))
+        ));
+    }
+}
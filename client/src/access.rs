@@ -0,0 +1,75 @@
+use alloc::vec::Vec;
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    CLType, CLTyped,
+};
+
+/// Role of an account on a shared list, each role includes the rights of the roles before it.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Role {
+    /// Read the elements with "get"
+    Reader = 0,
+    /// Add, change and remove the elements
+    Writer = 1,
+    /// Configure, rename and delete the list, grant and revoke the roles of the other accounts
+    Owner = 2,
+}
+
+impl Role {
+    fn from_tag(tag: u8) -> Option<Role> {
+        match tag {
+            0 => Some(Role::Reader),
+            1 => Some(Role::Writer),
+            2 => Some(Role::Owner),
+            _ => None,
+        }
+    }
+}
+
+/// A role is passed to the contract as its U8 tag.
+impl CLTyped for Role {
+    fn cl_type() -> CLType {
+        CLType::U8
+    }
+}
+
+impl ToBytes for Role {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        (*self as u8).to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for Role {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        let role = Role::from_tag(tag).ok_or(bytesrepr::Error::Formatting)?;
+        Ok((role, remainder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn should_serialize_role_as_u8() {
+        for role in [Role::Reader, Role::Writer, Role::Owner].iter() {
+            let bytes = role.to_bytes().unwrap();
+
+            assert_eq!(bytes, vec![*role as u8]);
+            assert_eq!(bytesrepr::deserialize::<Role>(bytes).unwrap(), *role);
+        }
+        assert_eq!(
+            bytesrepr::deserialize::<Role>(vec![Role::Owner as u8 + 1]),
+            Err(bytesrepr::Error::Formatting)
+        );
+        assert!(Role::Owner > Role::Writer && Role::Writer > Role::Reader);
+    }
+}
@@ -0,0 +1,427 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use casper_types::{account::AccountHash, bytesrepr::ToBytes, CLTyped, PublicKey, RuntimeArgs};
+
+use crate::{
+    DeviceScope, Method, Operation, Role, SecretEntry, ACCOUNT_ARG_NAME, BENEFICIARY_ARG_NAME,
+    COUNTER_ARG_NAME, DATA_ARG_NAME, DEVICE_ARG_NAME, ENTRIES_ARG_NAME, EXPECTED_VERSION_ARG_NAME,
+    FORCE_ARG_NAME, GUARDIANS_ARG_NAME, ID_ARG_NAME, INACTIVITY_ARG_NAME, LABEL_ARG_NAME,
+    METHOD_ARG_NAME, NAMED_KEY_ARG_NAME, NEW_NAMED_KEY_ARG_NAME, NEW_OWNER_ARG_NAME,
+    OPERATIONS_ARG_NAME, PINNED_ARG_NAME, PURGE_ARG_NAME, RETENTION_ARG_NAME, ROLE_ARG_NAME,
+    SCOPE_ARG_NAME, STRICT_ARG_NAME, TAG_ARG_NAME, THRESHOLD_ARG_NAME, TIMELOCK_ARG_NAME,
+    URIS_ARG_NAME,
+};
+
+/// Arguments of a call to the contract.
+///
+/// The same arguments are used by the session code, which also needs the "method" argument, and by
+/// the entry points of the stored contract, whose name is the method.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuthenticatorArgs {
+    method: Method,
+    args: RuntimeArgs,
+}
+
+impl AuthenticatorArgs {
+    /// Arguments of a method on a list, without its optional arguments.
+    pub fn new(method: Method, named_key: &str) -> Self {
+        let mut authenticator_args = AuthenticatorArgs {
+            method,
+            args: RuntimeArgs::new(),
+        };
+        authenticator_args.insert(NAMED_KEY_ARG_NAME, String::from(named_key));
+        authenticator_args
+    }
+
+    /// Add or update the elements "[ID];[VALUE]".
+    pub fn add<S: AsRef<str>>(named_key: &str, entries: &[S]) -> Self {
+        AuthenticatorArgs::new(Method::Add, named_key).with_entries(entries)
+    }
+
+    /// Add or update structured elements.
+    pub fn add_entries(named_key: &str, entries: &[SecretEntry]) -> Self {
+        AuthenticatorArgs::new(Method::Add, named_key).with_secret_entries(entries)
+    }
+
+    /// Add or update elements given as otpauth URIs, the contract converts them.
+    pub fn add_uris<S: AsRef<str>>(named_key: &str, uris: &[S]) -> Self {
+        AuthenticatorArgs::new(Method::Add, named_key).with_uris(uris)
+    }
+
+    /// Add the elements "[ID];[VALUE]", the call is reverted if an ID already exists. The
+    /// structured elements and the URIs are set with `with_secret_entries` and `with_uris`.
+    pub fn insert<S: AsRef<str>>(named_key: &str, entries: &[S]) -> Self {
+        AuthenticatorArgs::new(Method::Insert, named_key).with_entries(entries)
+    }
+
+    /// Replace the elements "[ID];[VALUE]", the call is reverted if an ID doesn't exist.
+    pub fn update<S: AsRef<str>>(named_key: &str, entries: &[S]) -> Self {
+        AuthenticatorArgs::new(Method::Update, named_key).with_entries(entries)
+    }
+
+    /// Move the elements to the trash, given as "[ID]" or "[ID];[VALUE]".
+    pub fn del<S: AsRef<str>>(named_key: &str, entries: &[S]) -> Self {
+        AuthenticatorArgs::new(Method::Del, named_key).with_entries(entries)
+    }
+
+    /// Move all the elements to the trash, see `with_force`.
+    pub fn delall(named_key: &str) -> Self {
+        AuthenticatorArgs::new(Method::DelAll, named_key)
+    }
+
+    /// Move the elements back from the trash, given as "[ID]" or "[ID];[VALUE]".
+    pub fn restore<S: AsRef<str>>(named_key: &str, entries: &[S]) -> Self {
+        AuthenticatorArgs::new(Method::Restore, named_key).with_entries(entries)
+    }
+
+    /// Erase the trashed elements whose retention period is over. `with_entries` only erases the
+    /// given IDs, and the call is reverted if one of them is still retained.
+    pub fn purge(named_key: &str) -> Self {
+        AuthenticatorArgs::new(Method::Purge, named_key)
+    }
+
+    /// Convert a list created by a previous version of the contract.
+    pub fn migrate(named_key: &str) -> Self {
+        AuthenticatorArgs::new(Method::Migrate, named_key)
+    }
+
+    /// Return the elements, only useful with the stored contract.
+    pub fn get(named_key: &str) -> Self {
+        AuthenticatorArgs::new(Method::Get, named_key)
+    }
+
+    /// Change the settings of the list, see `with_strict`.
+    pub fn configure(named_key: &str) -> Self {
+        AuthenticatorArgs::new(Method::Configure, named_key)
+    }
+
+    /// Move the counter of a counter-based element to the next password, which is returned.
+    pub fn increment(named_key: &str, id: &str) -> Self {
+        let mut authenticator_args = AuthenticatorArgs::new(Method::Increment, named_key);
+        authenticator_args.insert(ID_ARG_NAME, String::from(id));
+        authenticator_args
+    }
+
+    /// Move the counter of a counter-based element to the counter observed on a device. The
+    /// counter never moves back.
+    pub fn resync(named_key: &str, id: &str, counter: u64) -> Self {
+        let mut authenticator_args = AuthenticatorArgs::new(Method::Resync, named_key);
+        authenticator_args.insert(ID_ARG_NAME, String::from(id));
+        authenticator_args.insert(COUNTER_ARG_NAME, counter);
+        authenticator_args
+    }
+
+    /// Return the names of the lists created by the contract, only useful with the stored
+    /// contract. This method doesnt take a named key.
+    pub fn lists() -> Self {
+        AuthenticatorArgs {
+            method: Method::Lists,
+            args: RuntimeArgs::new(),
+        }
+    }
+
+    /// Move the list to a new name, which must not be used yet.
+    pub fn rename(named_key: &str, new_named_key: &str) -> Self {
+        let mut authenticator_args = AuthenticatorArgs::new(Method::Rename, named_key);
+        authenticator_args.insert(NEW_NAMED_KEY_ARG_NAME, String::from(new_named_key));
+        authenticator_args
+    }
+
+    /// Copy the elements of the list into another list, created if it doesnt exist.
+    pub fn copy(named_key: &str, new_named_key: &str) -> Self {
+        let mut authenticator_args = AuthenticatorArgs::new(Method::Copy, named_key);
+        authenticator_args.insert(NEW_NAMED_KEY_ARG_NAME, String::from(new_named_key));
+        authenticator_args
+    }
+
+    /// Erase the elements of the list and remove its named keys.
+    pub fn dellist(named_key: &str) -> Self {
+        AuthenticatorArgs::new(Method::DelList, named_key)
+    }
+
+    /// Apply the operations in their order, in a single deploy. The whole batch is reverted if one
+    /// operation is invalid.
+    pub fn batch(named_key: &str, operations: &[Operation]) -> Self {
+        let mut authenticator_args = AuthenticatorArgs::new(Method::Batch, named_key);
+        authenticator_args.insert(OPERATIONS_ARG_NAME, operations.to_vec());
+        authenticator_args
+    }
+
+    /// Put the elements in a new order, given as the IDs of all the elements of the list.
+    pub fn reorder<S: AsRef<str>>(named_key: &str, ids: &[S]) -> Self {
+        AuthenticatorArgs::new(Method::Reorder, named_key).with_entries(ids)
+    }
+
+    /// Pin an element, so it is shown before the others, or unpin it.
+    pub fn pin(named_key: &str, id: &str, pinned: bool) -> Self {
+        let mut authenticator_args = AuthenticatorArgs::new(Method::Pin, named_key);
+        authenticator_args.insert(ID_ARG_NAME, String::from(id));
+        authenticator_args.insert(PINNED_ARG_NAME, pinned);
+        authenticator_args
+    }
+
+    /// Give a role on a shared list to an account, or change its role. Only an owner of the list
+    /// can call it.
+    pub fn grant(named_key: &str, account: AccountHash, role: Role) -> Self {
+        let mut authenticator_args = AuthenticatorArgs::new(Method::Grant, named_key);
+        authenticator_args.insert(ACCOUNT_ARG_NAME, account);
+        authenticator_args.insert(ROLE_ARG_NAME, role);
+        authenticator_args
+    }
+
+    /// Remove the role of an account on a shared list. Only an owner of the list can call it.
+    pub fn revoke(named_key: &str, account: AccountHash) -> Self {
+        let mut authenticator_args = AuthenticatorArgs::new(Method::Revoke, named_key);
+        authenticator_args.insert(ACCOUNT_ARG_NAME, account);
+        authenticator_args
+    }
+
+    /// Allow a key to call the list, or change its label and scope. Once a list has a device, the
+    /// deploys must be signed by one of its devices.
+    pub fn add_device(
+        named_key: &str,
+        public_key: PublicKey,
+        label: &str,
+        scope: DeviceScope,
+    ) -> Self {
+        let mut authenticator_args = AuthenticatorArgs::new(Method::AddDevice, named_key);
+        authenticator_args.insert(DEVICE_ARG_NAME, public_key);
+        authenticator_args.insert(LABEL_ARG_NAME, String::from(label));
+        authenticator_args.insert(SCOPE_ARG_NAME, scope);
+        authenticator_args
+    }
+
+    /// Remove a key from the devices of the list.
+    pub fn del_device(named_key: &str, public_key: PublicKey) -> Self {
+        let mut authenticator_args = AuthenticatorArgs::new(Method::DelDevice, named_key);
+        authenticator_args.insert(DEVICE_ARG_NAME, public_key);
+        authenticator_args
+    }
+
+    /// Choose the guardians of a shared list. `threshold` guardians can give the list to a new
+    /// owner, `timelock` milliseconds after their last approval. A pending recovery is cancelled.
+    pub fn set_guardians(
+        named_key: &str,
+        guardians: &[AccountHash],
+        threshold: u32,
+        timelock: u64,
+    ) -> Self {
+        let mut authenticator_args = AuthenticatorArgs::new(Method::SetGuardians, named_key);
+        authenticator_args.insert(GUARDIANS_ARG_NAME, guardians.to_vec());
+        authenticator_args.insert(THRESHOLD_ARG_NAME, threshold);
+        authenticator_args.insert(TIMELOCK_ARG_NAME, timelock);
+        authenticator_args
+    }
+
+    /// Approve, as a guardian, giving the list to a new owner.
+    pub fn approve_recovery(named_key: &str, new_owner: AccountHash) -> Self {
+        let mut authenticator_args = AuthenticatorArgs::new(Method::ApproveRecovery, named_key);
+        authenticator_args.insert(NEW_OWNER_ARG_NAME, new_owner);
+        authenticator_args
+    }
+
+    /// Cancel the pending recovery, as an owner.
+    pub fn cancel_recovery(named_key: &str) -> Self {
+        AuthenticatorArgs::new(Method::CancelRecovery, named_key)
+    }
+
+    /// Give the list to the new owner once the recovery is approved and the timelock is over.
+    pub fn complete_recovery(named_key: &str) -> Self {
+        AuthenticatorArgs::new(Method::CompleteRecovery, named_key)
+    }
+
+    /// Choose the beneficiary of a shared list, who gets `role` once the owner made no "add", "del"
+    /// or "heartbeat" call for `inactivity` milliseconds.
+    pub fn set_beneficiary(
+        named_key: &str,
+        beneficiary: AccountHash,
+        role: Role,
+        inactivity: u64,
+    ) -> Self {
+        let mut authenticator_args = AuthenticatorArgs::new(Method::SetBeneficiary, named_key);
+        authenticator_args.insert(BENEFICIARY_ARG_NAME, beneficiary);
+        authenticator_args.insert(ROLE_ARG_NAME, role);
+        authenticator_args.insert(INACTIVITY_ARG_NAME, inactivity);
+        authenticator_args
+    }
+
+    /// Tell the contract that the owner is still active, without changing the list.
+    pub fn heartbeat(named_key: &str) -> Self {
+        AuthenticatorArgs::new(Method::Heartbeat, named_key)
+    }
+
+    /// Claim the list, as the beneficiary, once the owner is inactive.
+    pub fn claim(named_key: &str) -> Self {
+        AuthenticatorArgs::new(Method::Claim, named_key)
+    }
+
+    /// Set the elements of "add", "del", "restore", "purge" and "reorder".
+    pub fn with_entries<S: AsRef<str>>(mut self, entries: &[S]) -> Self {
+        let entries: Vec<String> = entries
+            .iter()
+            .map(|entry| String::from(entry.as_ref()))
+            .collect();
+        self.insert(DATA_ARG_NAME, entries);
+        self
+    }
+
+    /// Set the structured elements of "add", in addition to the "[ID];[VALUE]" elements.
+    pub fn with_secret_entries(mut self, entries: &[SecretEntry]) -> Self {
+        self.insert(ENTRIES_ARG_NAME, entries.to_vec());
+        self
+    }
+
+    /// Set the otpauth URIs of "add", in addition to the other elements.
+    pub fn with_uris<S: AsRef<str>>(mut self, uris: &[S]) -> Self {
+        let uris: Vec<String> = uris.iter().map(|uri| String::from(uri.as_ref())).collect();
+        self.insert(URIS_ARG_NAME, uris);
+        self
+    }
+
+    /// Also erase the IDs from the index and empty the trash with "delall". The elements are erased
+    /// instead of moved to the trash.
+    pub fn with_purge(mut self, purge: bool) -> Self {
+        self.insert(PURGE_ARG_NAME, purge);
+        self
+    }
+
+    /// Erase the elements with "delall" instead of moving them to the trash.
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.insert(FORCE_ARG_NAME, force);
+        self
+    }
+
+    /// Only remove the elements carrying the tag, or in the folder of the same name, with "del"
+    /// and "delall". The elements are always moved to the trash.
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.insert(TAG_ARG_NAME, String::from(tag));
+        self
+    }
+
+    /// Time in milliseconds before a trashed element can be purged, with "configure".
+    pub fn with_retention(mut self, retention: u64) -> Self {
+        self.insert(RETENTION_ARG_NAME, retention);
+        self
+    }
+
+    /// Only accept encrypted elements in the list, with "configure".
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.insert(STRICT_ARG_NAME, strict);
+        self
+    }
+
+    /// Revert with a conflict if the version of the list is not the version read by the caller,
+    /// so the changes made by another device are not overwritten.
+    pub fn with_expected_version(mut self, version: u64) -> Self {
+        self.insert(EXPECTED_VERSION_ARG_NAME, version);
+        self
+    }
+
+    pub fn method(&self) -> Method {
+        self.method
+    }
+
+    /// Name of the entry point of the stored contract.
+    pub fn entry_point(&self) -> &'static str {
+        self.method.name()
+    }
+
+    /// Arguments of the entry point of the stored contract.
+    pub fn entry_point_args(&self) -> RuntimeArgs {
+        self.args.clone()
+    }
+
+    /// Arguments of the session code, the method is given as an argument.
+    pub fn session_args(&self) -> RuntimeArgs {
+        let mut session_args = self.args.clone();
+        session_args
+            .insert(METHOD_ARG_NAME, String::from(self.method.name()))
+            .expect("a String can always be serialized");
+        session_args
+    }
+
+    /// Content of the file given to "casper-client put-deploy --session-args-complex" to call the
+    /// session code. Each argument is written as the hex of its serialized CLValue.
+    #[cfg(feature = "std")]
+    pub fn to_json(&self) -> String {
+        let named_args: Vec<serde_json::Value> = self
+            .session_args()
+            .named_args()
+            .map(|named_arg| {
+                let raw_bytes = named_arg
+                    .cl_value()
+                    .to_bytes()
+                    .expect("a CLValue can always be serialized");
+                serde_json::json!({
+                    "name": named_arg.name(),
+                    "value": { "raw_bytes": hex::encode(raw_bytes) }
+                })
+            })
+            .collect();
+        format!("{:#}", serde_json::Value::Array(named_args))
+    }
+
+    fn insert<T: CLTyped + ToBytes>(&mut self, name: &str, value: T) {
+        self.args
+            .insert(name, value)
+            .expect("the arguments of the contract can always be serialized");
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    // Raw bytes of the README example : method "add", keys ["ID1;VALUE"], named-key "test"
+    const METHOD_RAW_BYTES: &str = "07000000030000006164640a";
+    const KEYS_RAW_BYTES: &str = "1100000001000000090000004944313b56414c55450e0a";
+    const NAMED_KEY_RAW_BYTES: &str = "0800000004000000746573740a";
+
+    #[test]
+    fn should_encode_args_as_casper_client_json() {
+        let json: serde_json::Value =
+            serde_json::from_str(&AuthenticatorArgs::add("test", &["ID1;VALUE"]).to_json())
+                .unwrap();
+        let raw_bytes = |name: &str| {
+            json.as_array()
+                .unwrap()
+                .iter()
+                .find(|named_arg| named_arg["name"] == name)
+                .and_then(|named_arg| named_arg["value"]["raw_bytes"].as_str())
+                .map(String::from)
+        };
+
+        assert_eq!(raw_bytes(METHOD_ARG_NAME).unwrap(), METHOD_RAW_BYTES);
+        assert_eq!(raw_bytes(DATA_ARG_NAME).unwrap(), KEYS_RAW_BYTES);
+        assert_eq!(raw_bytes(NAMED_KEY_ARG_NAME).unwrap(), NAMED_KEY_RAW_BYTES);
+    }
+
+    #[test]
+    fn should_only_give_method_to_session_code() {
+        let args = AuthenticatorArgs::delall("test").with_purge(true);
+        let arg = |args: RuntimeArgs, name: &str| args.get(name).cloned();
+
+        assert_eq!(args.entry_point(), "delall");
+        assert!(arg(args.entry_point_args(), METHOD_ARG_NAME).is_none());
+        assert_eq!(
+            arg(args.session_args(), METHOD_ARG_NAME)
+                .unwrap()
+                .into_t::<String>()
+                .unwrap(),
+            "delall"
+        );
+        assert!(arg(args.entry_point_args(), PURGE_ARG_NAME)
+            .unwrap()
+            .into_t::<bool>()
+            .unwrap());
+    }
+
+    #[test]
+    fn should_find_method_by_name() {
+        for method in Method::ALL.iter() {
+            assert_eq!(Method::from_name(method.name()), Some(*method));
+        }
+        assert_eq!(Method::from_name("dellall"), None);
+    }
+}
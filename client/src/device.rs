@@ -0,0 +1,147 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    CLType, CLTyped, PublicKey,
+};
+
+/// Methods a device can call on a list, each scope includes the methods of the scopes before it.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DeviceScope {
+    /// Read the elements with "get"
+    ReadOnly = 0,
+    /// Add new elements with "insert" or with the "add" operations of a batch, and move elements
+    /// into the list. The existing elements can't be replaced, changed or removed.
+    AddOnly = 1,
+    /// Every method, including the management of the devices
+    Full = 2,
+}
+
+impl DeviceScope {
+    fn from_tag(tag: u8) -> Option<DeviceScope> {
+        match tag {
+            0 => Some(DeviceScope::ReadOnly),
+            1 => Some(DeviceScope::AddOnly),
+            2 => Some(DeviceScope::Full),
+            _ => None,
+        }
+    }
+}
+
+/// A scope is passed to the contract as its U8 tag.
+impl CLTyped for DeviceScope {
+    fn cl_type() -> CLType {
+        CLType::U8
+    }
+}
+
+impl ToBytes for DeviceScope {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        (*self as u8).to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for DeviceScope {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        let scope = DeviceScope::from_tag(tag).ok_or(bytesrepr::Error::Formatting)?;
+        Ok((scope, remainder))
+    }
+}
+
+/// A key allowed to sign the deploys calling a list, usually an associated key of the account
+/// kept on one device.
+///
+/// Only the shared lists are protected by their devices. A list saved in the named keys of an
+/// account can be changed by any key of the account with its own session code, which doesn't
+/// check the devices.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Device {
+    pub public_key: PublicKey,
+    /// Name of the device chosen by the owner, "phone" or "laptop"
+    pub label: String,
+    pub scope: DeviceScope,
+}
+
+impl Device {
+    pub fn new(public_key: PublicKey, label: &str, scope: DeviceScope) -> Self {
+        Device {
+            public_key,
+            label: String::from(label),
+            scope,
+        }
+    }
+
+    /// Account hash of the key, as listed in the authorization keys of a deploy.
+    pub fn account_hash(&self) -> AccountHash {
+        AccountHash::from(&self.public_key)
+    }
+}
+
+impl CLTyped for Device {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for Device {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.public_key.to_bytes()?);
+        result.append(&mut self.label.to_bytes()?);
+        result.append(&mut self.scope.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.public_key.serialized_length()
+            + self.label.serialized_length()
+            + self.scope.serialized_length()
+    }
+}
+
+impl FromBytes for Device {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (public_key, remainder) = PublicKey::from_bytes(bytes)?;
+        let (label, remainder) = String::from_bytes(remainder)?;
+        let (scope, remainder) = DeviceScope::from_bytes(remainder)?;
+        let device = Device {
+            public_key,
+            label,
+            scope,
+        };
+        Ok((device, remainder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use casper_types::SecretKey;
+
+    #[test]
+    fn should_serialize_devices() {
+        let public_key =
+            |seed: u8| PublicKey::from(&SecretKey::ed25519_from_bytes([seed; 32]).unwrap());
+        let devices = vec![
+            Device::new(public_key(1), "phone", DeviceScope::ReadOnly),
+            Device::new(public_key(2), "laptop", DeviceScope::Full),
+        ];
+        let bytes = devices.to_bytes().unwrap();
+
+        assert_eq!(bytes.len(), devices.serialized_length());
+        assert_eq!(
+            bytesrepr::deserialize::<Vec<Device>>(bytes).unwrap(),
+            devices
+        );
+        assert_eq!(devices[0].account_hash(), AccountHash::from(&public_key(1)));
+    }
+}
@@ -0,0 +1,384 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    CLType, CLTyped,
+};
+
+/// Version of the serialization of `SecretEntry`, written before its fields so new fields can be
+/// added later
+const SECRET_ENTRY_VERSION: u8 = 3;
+/// First version, without the tags and the folder
+const SECRET_ENTRY_VERSION_1: u8 = 1;
+/// Version with the tags and the folder, without the pin
+const SECRET_ENTRY_VERSION_2: u8 = 2;
+
+/// Number of digits of a password when the entry doesn't say otherwise
+pub const DEFAULT_DIGITS: u8 = 6;
+/// Validity of a time-based password in seconds when the entry doesn't say otherwise
+pub const DEFAULT_PERIOD: u32 = 30;
+
+/// Hash function used to generate the passwords
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    Sha1 = 0,
+    Sha256 = 1,
+    Sha512 = 2,
+}
+
+impl Algorithm {
+    /// Name of the algorithm in the otpauth URIs.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Algorithm::Sha1 => "SHA1",
+            Algorithm::Sha256 => "SHA256",
+            Algorithm::Sha512 => "SHA512",
+        }
+    }
+
+    /// Get the algorithm from its name, in any case.
+    pub fn from_name(name: &str) -> Option<Algorithm> {
+        [Algorithm::Sha1, Algorithm::Sha256, Algorithm::Sha512]
+            .iter()
+            .copied()
+            .find(|algorithm| algorithm.name().eq_ignore_ascii_case(name))
+    }
+
+    fn from_tag(tag: u8) -> Option<Algorithm> {
+        match tag {
+            0 => Some(Algorithm::Sha1),
+            1 => Some(Algorithm::Sha256),
+            2 => Some(Algorithm::Sha512),
+            _ => None,
+        }
+    }
+}
+
+/// Moving factor of the passwords
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OtpKind {
+    /// Time-based (TOTP), a new password every `period` seconds
+    Totp = 0,
+    /// Counter-based (HOTP), a new password each time `counter` is incremented
+    Hotp = 1,
+}
+
+impl OtpKind {
+    fn from_tag(tag: u8) -> Option<OtpKind> {
+        match tag {
+            0 => Some(OtpKind::Totp),
+            1 => Some(OtpKind::Hotp),
+            _ => None,
+        }
+    }
+}
+
+/// An element of a list.
+///
+/// Before this type, an element was a "[ID];[VALUE]" string. A string is converted with
+/// `SecretEntry::new(ID, VALUE)`, and `to_legacy` gives it back.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SecretEntry {
+    /// Key of the element in the list, it can't contain ";"
+    pub id: String,
+    pub issuer: String,
+    pub account_label: String,
+    /// Base32 secret, or an encrypted envelope "v1;[nonce];[ciphertext]"
+    pub secret: String,
+    pub algorithm: Algorithm,
+    pub kind: OtpKind,
+    pub digits: u8,
+    /// Only used by the time-based passwords
+    pub period: u32,
+    /// Only used by the counter-based passwords
+    pub counter: u64,
+    /// Block time of the first save of the element, in milliseconds. Set by the contract.
+    pub created_at: u64,
+    /// Block time of the last save of the element, in milliseconds. Set by the contract.
+    pub updated_at: u64,
+    /// Labels chosen by the user, "work" or "old-job"
+    pub tags: Vec<String>,
+    /// Path of the folder of the element, "work/servers", empty at the root of the list
+    pub folder: String,
+    /// Pinned elements are shown before the others by "get". Set by the contract, with "pin".
+    pub pinned: bool,
+}
+
+impl SecretEntry {
+    /// A time-based entry with the default settings of the authenticator apps.
+    pub fn new(id: &str, secret: &str) -> Self {
+        SecretEntry {
+            id: String::from(id),
+            issuer: String::new(),
+            account_label: String::new(),
+            secret: String::from(secret),
+            algorithm: Algorithm::Sha1,
+            kind: OtpKind::Totp,
+            digits: DEFAULT_DIGITS,
+            period: DEFAULT_PERIOD,
+            counter: 0,
+            created_at: 0,
+            updated_at: 0,
+            tags: Vec::new(),
+            folder: String::new(),
+            pinned: false,
+        }
+    }
+
+    /// Check if the entry carries the tag, or is in the folder named by the tag or in one of its
+    /// subfolders.
+    pub fn matches_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|entry_tag| entry_tag == tag)
+            || self
+                .folder
+                .strip_prefix(tag)
+                .map_or(false, |rest| rest.is_empty() || rest.starts_with('/'))
+    }
+
+    /// Convert a "[ID];[VALUE]" string, None if there is no ";".
+    pub fn from_legacy(entry: &str) -> Option<Self> {
+        entry
+            .split_once(';')
+            .map(|(id, secret)| SecretEntry::new(id, secret))
+    }
+
+    /// The "[ID];[VALUE]" string of the entry, its metadata is lost.
+    pub fn to_legacy(&self) -> String {
+        format!("{};{}", self.id, self.secret)
+    }
+}
+
+impl CLTyped for SecretEntry {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for SecretEntry {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.push(SECRET_ENTRY_VERSION);
+        result.append(&mut self.id.to_bytes()?);
+        result.append(&mut self.issuer.to_bytes()?);
+        result.append(&mut self.account_label.to_bytes()?);
+        result.append(&mut self.secret.to_bytes()?);
+        result.push(self.algorithm as u8);
+        result.push(self.kind as u8);
+        result.push(self.digits);
+        result.append(&mut self.period.to_bytes()?);
+        result.append(&mut self.counter.to_bytes()?);
+        result.append(&mut self.created_at.to_bytes()?);
+        result.append(&mut self.updated_at.to_bytes()?);
+        result.append(&mut self.tags.to_bytes()?);
+        result.append(&mut self.folder.to_bytes()?);
+        result.append(&mut self.pinned.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + self.id.serialized_length()
+            + self.issuer.serialized_length()
+            + self.account_label.serialized_length()
+            + self.secret.serialized_length()
+            + 3 * U8_SERIALIZED_LENGTH
+            + self.period.serialized_length()
+            + self.counter.serialized_length()
+            + self.created_at.serialized_length()
+            + self.updated_at.serialized_length()
+            + self.tags.serialized_length()
+            + self.folder.serialized_length()
+            + self.pinned.serialized_length()
+    }
+}
+
+impl FromBytes for SecretEntry {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (version, remainder) = u8::from_bytes(bytes)?;
+        if version < SECRET_ENTRY_VERSION_1 || version > SECRET_ENTRY_VERSION {
+            return Err(bytesrepr::Error::Formatting);
+        }
+        let (id, remainder) = String::from_bytes(remainder)?;
+        let (issuer, remainder) = String::from_bytes(remainder)?;
+        let (account_label, remainder) = String::from_bytes(remainder)?;
+        let (secret, remainder) = String::from_bytes(remainder)?;
+        let (algorithm, remainder) = u8::from_bytes(remainder)?;
+        let algorithm = Algorithm::from_tag(algorithm).ok_or(bytesrepr::Error::Formatting)?;
+        let (kind, remainder) = u8::from_bytes(remainder)?;
+        let kind = OtpKind::from_tag(kind).ok_or(bytesrepr::Error::Formatting)?;
+        let (digits, remainder) = u8::from_bytes(remainder)?;
+        let (period, remainder) = u32::from_bytes(remainder)?;
+        let (counter, remainder) = u64::from_bytes(remainder)?;
+        let (created_at, remainder) = u64::from_bytes(remainder)?;
+        let (updated_at, remainder) = u64::from_bytes(remainder)?;
+        //The entries saved before the version 2 have no tag and are at the root of the list
+        let (tags, folder, remainder) = if version < SECRET_ENTRY_VERSION_2 {
+            (Vec::new(), String::new(), remainder)
+        } else {
+            let (tags, remainder) = Vec::<String>::from_bytes(remainder)?;
+            let (folder, remainder) = String::from_bytes(remainder)?;
+            (tags, folder, remainder)
+        };
+        //The entries saved before the version 3 are not pinned
+        let (pinned, remainder) = if version < SECRET_ENTRY_VERSION {
+            (false, remainder)
+        } else {
+            bool::from_bytes(remainder)?
+        };
+        let entry = SecretEntry {
+            id,
+            issuer,
+            account_label,
+            secret,
+            algorithm,
+            kind,
+            digits,
+            period,
+            counter,
+            created_at,
+            updated_at,
+            tags,
+            folder,
+            pinned,
+        };
+        Ok((entry, remainder))
+    }
+}
+
+/// An element removed by "del", kept in the trash of its list until it is restored or purged.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrashedEntry {
+    pub entry: SecretEntry,
+    /// Block time of the removal, in milliseconds. Set by the contract.
+    pub deleted_at: u64,
+}
+
+impl CLTyped for TrashedEntry {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for TrashedEntry {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.entry.to_bytes()?);
+        result.append(&mut self.deleted_at.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.entry.serialized_length() + self.deleted_at.serialized_length()
+    }
+}
+
+impl FromBytes for TrashedEntry {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (entry, remainder) = SecretEntry::from_bytes(bytes)?;
+        let (deleted_at, remainder) = u64::from_bytes(remainder)?;
+        Ok((TrashedEntry { entry, deleted_at }, remainder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn should_serialize_secret_entry() {
+        let entry = SecretEntry {
+            issuer: String::from("Casper"),
+            account_label: String::from("alice@example.com"),
+            algorithm: Algorithm::Sha256,
+            kind: OtpKind::Hotp,
+            digits: 8,
+            counter: 42,
+            created_at: 1,
+            updated_at: 2,
+            tags: vec![String::from("old-job")],
+            folder: String::from("work/servers"),
+            pinned: true,
+            ..SecretEntry::new("ID1", "JBSWY3DPEHPK3PXP")
+        };
+        let bytes = entry.to_bytes().unwrap();
+
+        assert_eq!(bytes.len(), entry.serialized_length());
+        assert_eq!(bytesrepr::deserialize::<SecretEntry>(bytes).unwrap(), entry);
+    }
+
+    #[test]
+    fn should_read_secret_entry_version_1() {
+        let entry = SecretEntry::new("ID1", "JBSWY3DPEHPK3PXP");
+        let mut bytes = entry.to_bytes().unwrap();
+        //The version 1 ends with the update date, before the tags, the folder and the pin
+        bytes.truncate(
+            bytes.len()
+                - entry.tags.serialized_length()
+                - entry.folder.serialized_length()
+                - entry.pinned.serialized_length(),
+        );
+        bytes[0] = SECRET_ENTRY_VERSION_1;
+
+        assert_eq!(bytesrepr::deserialize::<SecretEntry>(bytes).unwrap(), entry);
+    }
+
+    #[test]
+    fn should_read_secret_entry_version_2() {
+        let entry = SecretEntry {
+            tags: vec![String::from("old-job")],
+            folder: String::from("work/servers"),
+            ..SecretEntry::new("ID1", "JBSWY3DPEHPK3PXP")
+        };
+        let mut bytes = entry.to_bytes().unwrap();
+        //The version 2 ends with the folder, before the pin
+        bytes.truncate(bytes.len() - entry.pinned.serialized_length());
+        bytes[0] = SECRET_ENTRY_VERSION_2;
+
+        assert_eq!(bytesrepr::deserialize::<SecretEntry>(bytes).unwrap(), entry);
+    }
+
+    #[test]
+    fn should_match_tag_or_folder() {
+        let entry = SecretEntry {
+            tags: vec![String::from("old-job")],
+            folder: String::from("work/servers"),
+            ..SecretEntry::new("ID1", "JBSWY3DPEHPK3PXP")
+        };
+
+        assert!(entry.matches_tag("old-job"));
+        assert!(entry.matches_tag("work"));
+        assert!(entry.matches_tag("work/servers"));
+        assert!(!entry.matches_tag("wor"));
+        assert!(!entry.matches_tag("servers"));
+    }
+
+    #[test]
+    fn should_serialize_trashed_entry() {
+        let trashed_entry = TrashedEntry {
+            entry: SecretEntry::new("ID1", "JBSWY3DPEHPK3PXP"),
+            deleted_at: 42,
+        };
+        let bytes = trashed_entry.to_bytes().unwrap();
+
+        assert_eq!(bytes.len(), trashed_entry.serialized_length());
+        assert_eq!(
+            bytesrepr::deserialize::<TrashedEntry>(bytes).unwrap(),
+            trashed_entry
+        );
+    }
+
+    #[test]
+    fn should_keep_separator_in_legacy_value() {
+        let entry = SecretEntry::from_legacy("ID1;VALUE;WITH;SEPARATORS").unwrap();
+
+        assert_eq!(entry.id, "ID1");
+        assert_eq!(entry.secret, "VALUE;WITH;SEPARATORS");
+        assert_eq!(entry.to_legacy(), "ID1;VALUE;WITH;SEPARATORS");
+        assert_eq!(SecretEntry::from_legacy("ID1"), None);
+    }
+}
@@ -0,0 +1,215 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    crypto, CLType, CLTyped,
+};
+
+use crate::SecretEntry;
+
+/// Change of an element recorded in the history of a list
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// The element was saved with a new ID
+    Added = 0,
+    /// The element replaced the element having the same ID
+    Updated = 1,
+    /// The element was moved to the trash or erased
+    Deleted = 2,
+    /// The element was moved back from the trash
+    Restored = 3,
+    /// The element was erased from the trash
+    Purged = 4,
+    /// The list was renamed, the ID of the record is the previous name of the list
+    Renamed = 5,
+}
+
+impl ChangeKind {
+    fn from_tag(tag: u8) -> Option<ChangeKind> {
+        match tag {
+            0 => Some(ChangeKind::Added),
+            1 => Some(ChangeKind::Updated),
+            2 => Some(ChangeKind::Deleted),
+            3 => Some(ChangeKind::Restored),
+            4 => Some(ChangeKind::Purged),
+            5 => Some(ChangeKind::Renamed),
+            _ => None,
+        }
+    }
+}
+
+/// Blake2b hash of a serialized element
+pub type EntryDigest = [u8; 32];
+
+/// Get the digest of an element, as recorded by the contract in the history.
+pub fn entry_digest(entry: &SecretEntry) -> Result<EntryDigest, bytesrepr::Error> {
+    Ok(crypto::blake2b(entry.to_bytes()?))
+}
+
+/// A change of an element, appended to the history of its list by the contract.
+///
+/// The record doesn't hold the element, only its digest, so that the secret can't be read
+/// from the history once the element is purged.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChangeRecord {
+    /// Version of the list after the call that made the change
+    pub version: u64,
+    pub kind: ChangeKind,
+    pub id: String,
+    /// Block time of the call, in milliseconds
+    pub block_time: u64,
+    /// Account that sent the deploy
+    pub caller: AccountHash,
+    /// Digest of the element after the change, None when it left the list
+    pub digest: Option<EntryDigest>,
+}
+
+impl CLTyped for ChangeRecord {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for ChangeRecord {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.version.to_bytes()?);
+        result.push(self.kind as u8);
+        result.append(&mut self.id.to_bytes()?);
+        result.append(&mut self.block_time.to_bytes()?);
+        result.append(&mut self.caller.to_bytes()?);
+        result.append(&mut self.digest.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.version.serialized_length()
+            + U8_SERIALIZED_LENGTH
+            + self.id.serialized_length()
+            + self.block_time.serialized_length()
+            + self.caller.serialized_length()
+            + self.digest.serialized_length()
+    }
+}
+
+impl FromBytes for ChangeRecord {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (version, remainder) = u64::from_bytes(bytes)?;
+        let (kind, remainder) = u8::from_bytes(remainder)?;
+        let kind = ChangeKind::from_tag(kind).ok_or(bytesrepr::Error::Formatting)?;
+        let (id, remainder) = String::from_bytes(remainder)?;
+        let (block_time, remainder) = u64::from_bytes(remainder)?;
+        let (caller, remainder) = AccountHash::from_bytes(remainder)?;
+        let (digest, remainder) = Option::<EntryDigest>::from_bytes(remainder)?;
+        let record = ChangeRecord {
+            version,
+            kind,
+            id,
+            block_time,
+            caller,
+            digest,
+        };
+        Ok((record, remainder))
+    }
+}
+
+/// Get the IDs of the elements of a list as they were at a version, with the digest of each
+/// element, from its history.
+///
+/// The records must be in the order of the history. The history doesn't hold the elements, an
+/// element can be matched with the state by comparing its `entry_digest`.
+///
+/// The state can't restore a list : a previous value is only known from a backup of the element
+/// matching the digest, the contract doesn't keep it.
+pub fn state_at(records: &[ChangeRecord], version: u64) -> BTreeMap<String, EntryDigest> {
+    let mut digests: BTreeMap<String, EntryDigest> = BTreeMap::new();
+    for record in records
+        .iter()
+        .take_while(|record| record.version <= version)
+    {
+        match (record.kind, record.digest) {
+            (ChangeKind::Added, Some(digest))
+            | (ChangeKind::Updated, Some(digest))
+            | (ChangeKind::Restored, Some(digest)) => {
+                digests.insert(record.id.clone(), digest);
+            }
+            (ChangeKind::Deleted, _) => {
+                digests.remove(&record.id);
+            }
+            //A purged element was already out of the list, and a renamed list keeps its elements
+            _ => {}
+        }
+    }
+    digests
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn record(version: u64, kind: ChangeKind, entry: SecretEntry) -> ChangeRecord {
+        ChangeRecord {
+            version,
+            kind,
+            id: entry.id.clone(),
+            block_time: version * 1_000,
+            caller: AccountHash::new([7u8; 32]),
+            digest: match kind {
+                ChangeKind::Deleted | ChangeKind::Purged | ChangeKind::Renamed => None,
+                _ => Some(entry_digest(&entry).unwrap()),
+            },
+        }
+    }
+
+    #[test]
+    fn should_serialize_change_record() {
+        let records = vec![
+            record(1, ChangeKind::Added, SecretEntry::new("ID1", "VALUE1")),
+            record(2, ChangeKind::Deleted, SecretEntry::new("ID1", "VALUE1")),
+        ];
+        let bytes = records.to_bytes().unwrap();
+
+        assert_eq!(bytes.len(), records.serialized_length());
+        assert_eq!(
+            bytesrepr::deserialize::<Vec<ChangeRecord>>(bytes).unwrap(),
+            records
+        );
+    }
+
+    #[test]
+    fn should_rebuild_state_at_version() {
+        let records = vec![
+            record(1, ChangeKind::Added, SecretEntry::new("ID2", "VALUE2")),
+            record(1, ChangeKind::Added, SecretEntry::new("ID1", "VALUE1")),
+            record(2, ChangeKind::Updated, SecretEntry::new("ID1", "NEW")),
+            record(3, ChangeKind::Deleted, SecretEntry::new("ID2", "VALUE2")),
+            record(4, ChangeKind::Restored, SecretEntry::new("ID2", "VALUE2")),
+            record(5, ChangeKind::Deleted, SecretEntry::new("ID2", "VALUE2")),
+            record(6, ChangeKind::Purged, SecretEntry::new("ID2", "VALUE2")),
+            record(7, ChangeKind::Renamed, SecretEntry::new("old-key", "")),
+        ];
+        let digest = |secret_code: &str| {
+            let (id, value) = secret_code.split_once(';').unwrap();
+            (
+                String::from(id),
+                entry_digest(&SecretEntry::new(id, value)).unwrap(),
+            )
+        };
+        let state = |secret_codes: &[&str]| -> BTreeMap<String, EntryDigest> {
+            secret_codes.iter().map(|code| digest(code)).collect()
+        };
+
+        assert!(state_at(&records, 0).is_empty());
+        assert_eq!(state_at(&records, 1), state(&["ID1;VALUE1", "ID2;VALUE2"]));
+        assert_eq!(state_at(&records, 2), state(&["ID1;NEW", "ID2;VALUE2"]));
+        assert_eq!(state_at(&records, 3), state(&["ID1;NEW"]));
+        assert_eq!(state_at(&records, 4), state(&["ID1;NEW", "ID2;VALUE2"]));
+        assert_eq!(state_at(&records, 6), state(&["ID1;NEW"]));
+        assert_eq!(state_at(&records, 7), state(&["ID1;NEW"]));
+    }
+}
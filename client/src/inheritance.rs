@@ -0,0 +1,99 @@
+use alloc::vec::Vec;
+
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped,
+};
+
+use crate::Role;
+
+/// Beneficiary of a shared list, who can claim it when its owner stops calling it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Inheritance {
+    /// Owner who chose the beneficiary, only its calls count as activity
+    pub owner: AccountHash,
+    pub beneficiary: AccountHash,
+    /// Role given to the beneficiary by the claim, `Role::Reader` to read the elements or
+    /// `Role::Owner` to take the list
+    pub role: Role,
+    /// Time in milliseconds without activity of the owner before the beneficiary can claim
+    pub inactivity: u64,
+    /// Block time of the last "add", "del" or "heartbeat" of the owner
+    pub last_activity: u64,
+}
+
+impl Inheritance {
+    /// Block time from which the beneficiary can claim the list.
+    pub fn claimable_at(&self) -> u64 {
+        self.last_activity.saturating_add(self.inactivity)
+    }
+}
+
+impl CLTyped for Inheritance {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for Inheritance {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.owner.to_bytes()?);
+        result.append(&mut self.beneficiary.to_bytes()?);
+        result.append(&mut self.role.to_bytes()?);
+        result.append(&mut self.inactivity.to_bytes()?);
+        result.append(&mut self.last_activity.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.owner.serialized_length()
+            + self.beneficiary.serialized_length()
+            + self.role.serialized_length()
+            + self.inactivity.serialized_length()
+            + self.last_activity.serialized_length()
+    }
+}
+
+impl FromBytes for Inheritance {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (owner, remainder) = AccountHash::from_bytes(bytes)?;
+        let (beneficiary, remainder) = AccountHash::from_bytes(remainder)?;
+        let (role, remainder) = Role::from_bytes(remainder)?;
+        let (inactivity, remainder) = u64::from_bytes(remainder)?;
+        let (last_activity, remainder) = u64::from_bytes(remainder)?;
+        let inheritance = Inheritance {
+            owner,
+            beneficiary,
+            role,
+            inactivity,
+            last_activity,
+        };
+        Ok((inheritance, remainder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_serialize_inheritance() {
+        let inheritance = Inheritance {
+            owner: AccountHash::new([1u8; 32]),
+            beneficiary: AccountHash::new([2u8; 32]),
+            role: Role::Reader,
+            inactivity: 31_536_000_000,
+            last_activity: 1_000,
+        };
+        let bytes = inheritance.to_bytes().unwrap();
+
+        assert_eq!(bytes.len(), inheritance.serialized_length());
+        assert_eq!(
+            bytesrepr::deserialize::<Inheritance>(bytes).unwrap(),
+            inheritance
+        );
+        assert_eq!(inheritance.claimable_at(), 31_536_001_000);
+    }
+}
@@ -0,0 +1,211 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Names shared by the Blockchain Authenticator contract and its clients, and builders of the
+//! arguments of each method.
+//!
+//! ```ignore
+//! let args = AuthenticatorArgs::add("my-named-key", &["ID1;VALUE"]);
+//! // Session code (contract.wasm)
+//! let session_args = args.session_args();
+//! // Stored contract (installer.wasm)
+//! let (entry_point, entry_point_args) = (args.entry_point(), args.entry_point_args());
+//! // File for "casper-client put-deploy --session-args-complex"
+//! let json = args.to_json();
+//! ```
+
+extern crate alloc;
+
+mod access;
+mod args;
+mod device;
+mod entry;
+pub mod history;
+mod inheritance;
+mod operation;
+mod otpauth;
+mod recovery;
+
+pub use access::Role;
+pub use args::AuthenticatorArgs;
+pub use device::{Device, DeviceScope};
+pub use entry::{Algorithm, OtpKind, SecretEntry, TrashedEntry, DEFAULT_DIGITS, DEFAULT_PERIOD};
+pub use history::{ChangeKind, ChangeRecord};
+pub use inheritance::Inheritance;
+pub use operation::Operation;
+pub use otpauth::OtpauthError;
+pub use recovery::{Recovery, RecoveryRequest};
+
+/// Name of the method to run, only used by the session code
+pub const METHOD_ARG_NAME: &str = "method";
+/// Name of the list
+pub const NAMED_KEY_ARG_NAME: &str = "named-key";
+/// Elements "[ID];[VALUE]" to add, or IDs to remove
+pub const DATA_ARG_NAME: &str = "keys";
+/// Elements to add, as `SecretEntry`
+pub const ENTRIES_ARG_NAME: &str = "entries";
+/// Elements to add, as otpauth URIs
+pub const URIS_ARG_NAME: &str = "uris";
+/// Optional flag of "delall"
+pub const PURGE_ARG_NAME: &str = "purge";
+/// Optional flag of "delall", erase the elements instead of moving them to the trash
+pub const FORCE_ARG_NAME: &str = "force";
+/// Optional filter of "del" and "delall", only the elements carrying the tag or in its folder
+pub const TAG_ARG_NAME: &str = "tag";
+/// Optional flag of "configure"
+pub const STRICT_ARG_NAME: &str = "strict";
+/// Optional setting of "configure", time in milliseconds before a trashed element can be purged
+pub const RETENTION_ARG_NAME: &str = "retention";
+/// ID of the element of "increment", "resync" and "pin"
+pub const ID_ARG_NAME: &str = "id";
+/// Counter observed on the device, for "resync"
+pub const COUNTER_ARG_NAME: &str = "counter";
+/// Flag of "pin", false to unpin the element
+pub const PINNED_ARG_NAME: &str = "pinned";
+/// Name of the other list of "rename" and "copy"
+pub const NEW_NAMED_KEY_ARG_NAME: &str = "new-named-key";
+/// Operations of "batch", as `Operation`
+pub const OPERATIONS_ARG_NAME: &str = "operations";
+/// Optional version of the list read by the caller, checked by every method on a list
+pub const EXPECTED_VERSION_ARG_NAME: &str = "expected-version";
+/// Account of "grant" and "revoke"
+pub const ACCOUNT_ARG_NAME: &str = "account";
+/// Role given by "grant", as `Role`
+pub const ROLE_ARG_NAME: &str = "role";
+/// Public key of the device of "adddevice" and "deldevice"
+pub const DEVICE_ARG_NAME: &str = "device";
+/// Name of the device of "adddevice"
+pub const LABEL_ARG_NAME: &str = "label";
+/// Methods allowed to the device of "adddevice", as `DeviceScope`
+pub const SCOPE_ARG_NAME: &str = "scope";
+/// Accounts of "setguardians"
+pub const GUARDIANS_ARG_NAME: &str = "guardians";
+/// Number of guardians who must approve a recovery, with "setguardians"
+pub const THRESHOLD_ARG_NAME: &str = "threshold";
+/// Time in milliseconds between the approval and the recovery, with "setguardians"
+pub const TIMELOCK_ARG_NAME: &str = "timelock";
+/// Account of "approverecovery", which becomes the owner of the list
+pub const NEW_OWNER_ARG_NAME: &str = "new-owner";
+/// Account of "setbeneficiary", which can claim the list when its owner is inactive
+pub const BENEFICIARY_ARG_NAME: &str = "beneficiary";
+/// Time in milliseconds without activity of the owner before the claim, with "setbeneficiary"
+pub const INACTIVITY_ARG_NAME: &str = "inactivity";
+
+/// Entry point of the stored contract running a method on a shared list, owned by the contract
+/// package instead of the caller. It takes the arguments of the session code, with the method.
+pub const SHARED_ENTRY_POINT: &str = "shared";
+
+/// Operations of the contract. The name of a method is the value of the "method" parameter of
+/// the session code and the name of the entry point of the stored contract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Method {
+    Add,
+    Del,
+    DelAll,
+    Migrate,
+    Get,
+    Configure,
+    Increment,
+    Resync,
+    Lists,
+    Rename,
+    Copy,
+    DelList,
+    Batch,
+    Insert,
+    Update,
+    Restore,
+    Purge,
+    Grant,
+    Revoke,
+    AddDevice,
+    DelDevice,
+    SetGuardians,
+    ApproveRecovery,
+    CancelRecovery,
+    CompleteRecovery,
+    SetBeneficiary,
+    Heartbeat,
+    Claim,
+    Reorder,
+    Pin,
+}
+
+impl Method {
+    /// Every method, in the order of the entry points of the stored contract
+    pub const ALL: [Method; 30] = [
+        Method::Add,
+        Method::Del,
+        Method::DelAll,
+        Method::Migrate,
+        Method::Get,
+        Method::Configure,
+        Method::Increment,
+        Method::Resync,
+        Method::Lists,
+        Method::Rename,
+        Method::Copy,
+        Method::DelList,
+        Method::Batch,
+        Method::Insert,
+        Method::Update,
+        Method::Restore,
+        Method::Purge,
+        Method::Grant,
+        Method::Revoke,
+        Method::AddDevice,
+        Method::DelDevice,
+        Method::SetGuardians,
+        Method::ApproveRecovery,
+        Method::CancelRecovery,
+        Method::CompleteRecovery,
+        Method::SetBeneficiary,
+        Method::Heartbeat,
+        Method::Claim,
+        Method::Reorder,
+        Method::Pin,
+    ];
+
+    /// Get the method from its name, None if the name is unknown.
+    pub fn from_name(name: &str) -> Option<Method> {
+        Method::ALL
+            .iter()
+            .copied()
+            .find(|method| method.name() == name)
+    }
+
+    /// Get the name of the method.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Method::Add => "add",
+            Method::Del => "del",
+            Method::DelAll => "delall",
+            Method::Migrate => "migrate",
+            Method::Get => "get",
+            Method::Configure => "configure",
+            Method::Increment => "increment",
+            Method::Resync => "resync",
+            Method::Lists => "lists",
+            Method::Rename => "rename",
+            Method::Copy => "copy",
+            Method::DelList => "dellist",
+            Method::Batch => "batch",
+            Method::Insert => "insert",
+            Method::Update => "update",
+            Method::Restore => "restore",
+            Method::Purge => "purge",
+            Method::Grant => "grant",
+            Method::Revoke => "revoke",
+            Method::AddDevice => "adddevice",
+            Method::DelDevice => "deldevice",
+            Method::SetGuardians => "setguardians",
+            Method::ApproveRecovery => "approverecovery",
+            Method::CancelRecovery => "cancelrecovery",
+            Method::CompleteRecovery => "completerecovery",
+            Method::SetBeneficiary => "setbeneficiary",
+            Method::Heartbeat => "heartbeat",
+            Method::Claim => "claim",
+            Method::Reorder => "reorder",
+            Method::Pin => "pin",
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    CLType, CLTyped,
+};
+
+use crate::SecretEntry;
+
+const ADD_TAG: u8 = 0;
+const UPSERT_TAG: u8 = 1;
+const DELETE_TAG: u8 = 2;
+const RENAME_ID_TAG: u8 = 3;
+const MOVE_TAG: u8 = 4;
+
+/// A change of a list, applied with the other operations of a "batch" in a single deploy.
+///
+/// The operations are applied in their order. If one of them is invalid, the whole batch is
+/// reverted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Operation {
+    /// Add an element, no element of the list can have the same ID
+    Add(SecretEntry),
+    /// Add an element, or replace the element having the same ID
+    Upsert(SecretEntry),
+    /// Move an element to the trash, it must exist
+    Delete(String),
+    /// Change the ID of an element, no element of the list can have the new ID. The ID is the
+    /// associated data of an envelope, so an encrypted element must come with its secret sealed
+    /// again with the new ID, which replaces the secret of the element.
+    RenameId {
+        id: String,
+        new_id: String,
+        secret: Option<String>,
+    },
+    /// Move an element to another list, created if it doesn't exist. No element of the other list
+    /// can have the same ID.
+    Move { id: String, named_key: String },
+}
+
+impl Operation {
+    /// ID of the element changed by the operation.
+    pub fn id(&self) -> &str {
+        match self {
+            Operation::Add(entry) | Operation::Upsert(entry) => &entry.id,
+            Operation::Delete(id) | Operation::RenameId { id, .. } | Operation::Move { id, .. } => {
+                id
+            }
+        }
+    }
+
+    fn tag(&self) -> u8 {
+        match self {
+            Operation::Add(_) => ADD_TAG,
+            Operation::Upsert(_) => UPSERT_TAG,
+            Operation::Delete(_) => DELETE_TAG,
+            Operation::RenameId { .. } => RENAME_ID_TAG,
+            Operation::Move { .. } => MOVE_TAG,
+        }
+    }
+}
+
+impl CLTyped for Operation {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for Operation {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.push(self.tag());
+        match self {
+            Operation::Add(entry) | Operation::Upsert(entry) => {
+                result.append(&mut entry.to_bytes()?)
+            }
+            Operation::Delete(id) => result.append(&mut id.to_bytes()?),
+            Operation::RenameId { id, new_id, secret } => {
+                result.append(&mut id.to_bytes()?);
+                result.append(&mut new_id.to_bytes()?);
+                result.append(&mut secret.to_bytes()?);
+            }
+            Operation::Move { id, named_key } => {
+                result.append(&mut id.to_bytes()?);
+                result.append(&mut named_key.to_bytes()?);
+            }
+        }
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                Operation::Add(entry) | Operation::Upsert(entry) => entry.serialized_length(),
+                Operation::Delete(id) => id.serialized_length(),
+                Operation::RenameId { id, new_id, secret } => {
+                    id.serialized_length() + new_id.serialized_length() + secret.serialized_length()
+                }
+                Operation::Move { id, named_key } => {
+                    id.serialized_length() + named_key.serialized_length()
+                }
+            }
+    }
+}
+
+impl FromBytes for Operation {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        match tag {
+            ADD_TAG => {
+                let (entry, remainder) = SecretEntry::from_bytes(remainder)?;
+                Ok((Operation::Add(entry), remainder))
+            }
+            UPSERT_TAG => {
+                let (entry, remainder) = SecretEntry::from_bytes(remainder)?;
+                Ok((Operation::Upsert(entry), remainder))
+            }
+            DELETE_TAG => {
+                let (id, remainder) = String::from_bytes(remainder)?;
+                Ok((Operation::Delete(id), remainder))
+            }
+            RENAME_ID_TAG => {
+                let (id, remainder) = String::from_bytes(remainder)?;
+                let (new_id, remainder) = String::from_bytes(remainder)?;
+                let (secret, remainder) = Option::<String>::from_bytes(remainder)?;
+                Ok((Operation::RenameId { id, new_id, secret }, remainder))
+            }
+            MOVE_TAG => {
+                let (id, remainder) = String::from_bytes(remainder)?;
+                let (named_key, remainder) = String::from_bytes(remainder)?;
+                Ok((Operation::Move { id, named_key }, remainder))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn should_serialize_operations() {
+        let operations = vec![
+            Operation::Add(SecretEntry::new("ID1", "JBSWY3DPEHPK3PXP")),
+            Operation::Upsert(SecretEntry::new("ID2", "JBSWY3DPEHPK3PXP")),
+            Operation::Delete(String::from("ID3")),
+            Operation::RenameId {
+                id: String::from("ID4"),
+                new_id: String::from("ID5"),
+                secret: None,
+            },
+            Operation::RenameId {
+                id: String::from("ID5"),
+                new_id: String::from("ID6"),
+                secret: Some(String::from("v1;000000000000000000000000;00")),
+            },
+            Operation::Move {
+                id: String::from("ID6"),
+                named_key: String::from("other-list"),
+            },
+        ];
+        let bytes = operations.to_bytes().unwrap();
+
+        assert_eq!(bytes.len(), operations.serialized_length());
+        assert_eq!(
+            bytesrepr::deserialize::<Vec<Operation>>(bytes).unwrap(),
+            operations
+        );
+    }
+
+    #[test]
+    fn should_reject_unknown_operation() {
+        assert_eq!(
+            bytesrepr::deserialize::<Operation>(vec![MOVE_TAG + 1]),
+            Err(bytesrepr::Error::Formatting)
+        );
+    }
+}
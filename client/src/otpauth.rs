@@ -0,0 +1,250 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::entry::{Algorithm, OtpKind, SecretEntry};
+
+const SCHEME: &str = "otpauth://";
+
+/// Reasons why an otpauth URI can't be imported
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OtpauthError {
+    /// The URI doesn't start with "otpauth://"
+    NotAnOtpauthUri,
+    /// The type is neither "totp" nor "hotp"
+    UnknownKind,
+    /// The label, used as the ID of the entry, is empty
+    MissingLabel,
+    /// The "secret" parameter is missing or empty
+    MissingSecret,
+    /// A counter-based URI has no "counter" parameter
+    MissingCounter,
+    /// The value of "algorithm", "digits", "period" or "counter" can't be read
+    InvalidParameter,
+    /// A percent-encoded sequence is invalid or isn't UTF-8
+    InvalidEncoding,
+}
+
+impl SecretEntry {
+    /// Import an URI of the Key Uri Format used by the QR codes of the authenticator apps :
+    /// "otpauth://[totp|hotp]/[issuer]:[account]?secret=[secret]&issuer=[issuer]&...".
+    ///
+    /// The decoded label "[issuer]:[account]" becomes the ID of the entry, so importing the
+    /// same QR code twice updates the same element. The "issuer" parameter wins over the issuer
+    /// of the label. Unknown parameters (such as "image") are ignored.
+    pub fn from_otpauth_uri(uri: &str) -> Result<Self, OtpauthError> {
+        let rest = match uri.get(..SCHEME.len()) {
+            Some(scheme) if scheme.eq_ignore_ascii_case(SCHEME) => &uri[SCHEME.len()..],
+            _ => return Err(OtpauthError::NotAnOtpauthUri),
+        };
+        let (kind, rest) = rest.split_once('/').ok_or(OtpauthError::NotAnOtpauthUri)?;
+        let kind = if kind.eq_ignore_ascii_case("totp") {
+            OtpKind::Totp
+        } else if kind.eq_ignore_ascii_case("hotp") {
+            OtpKind::Hotp
+        } else {
+            return Err(OtpauthError::UnknownKind);
+        };
+
+        let (label, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let label = decode(label)?;
+        if label.is_empty() {
+            return Err(OtpauthError::MissingLabel);
+        }
+        let mut entry = SecretEntry::new(&label, "");
+        entry.kind = kind;
+        match label.split_once(':') {
+            Some((issuer, account_label)) => {
+                entry.issuer = String::from(issuer);
+                entry.account_label = String::from(account_label.trim_start());
+            }
+            None => entry.account_label = label.clone(),
+        }
+
+        let mut counter = None;
+        for parameter in query.split('&').filter(|parameter| !parameter.is_empty()) {
+            let (name, value) = parameter.split_once('=').unwrap_or((parameter, ""));
+            let value = decode(value)?;
+            match name {
+                "secret" => entry.secret = value,
+                "issuer" => entry.issuer = value,
+                "algorithm" => {
+                    entry.algorithm =
+                        Algorithm::from_name(&value).ok_or(OtpauthError::InvalidParameter)?
+                }
+                "digits" => entry.digits = parse_number(&value)?,
+                "period" => entry.period = parse_number(&value)?,
+                "counter" => counter = Some(parse_number(&value)?),
+                _ => {}
+            }
+        }
+
+        if entry.secret.is_empty() {
+            return Err(OtpauthError::MissingSecret);
+        }
+        if kind == OtpKind::Hotp {
+            entry.counter = counter.ok_or(OtpauthError::MissingCounter)?;
+        }
+        Ok(entry)
+    }
+
+    /// Export the entry as an URI that the authenticator apps can import. An entry without
+    /// account label uses its ID as label.
+    ///
+    /// The secret is exported as it is stored : an encrypted secret must be opened first.
+    pub fn to_otpauth_uri(&self) -> String {
+        let account_label = if self.account_label.is_empty() {
+            &self.id
+        } else {
+            &self.account_label
+        };
+        let (kind, moving_factor) = match self.kind {
+            OtpKind::Totp => ("totp", format!("period={}", self.period)),
+            OtpKind::Hotp => ("hotp", format!("counter={}", self.counter)),
+        };
+
+        let mut uri = format!("{}{}/", SCHEME, kind);
+        if !self.issuer.is_empty() {
+            uri.push_str(&encode(&self.issuer));
+            uri.push(':');
+        }
+        uri.push_str(&encode(account_label));
+        uri.push_str(&format!("?secret={}", encode(&self.secret)));
+        if !self.issuer.is_empty() {
+            uri.push_str(&format!("&issuer={}", encode(&self.issuer)));
+        }
+        uri.push_str(&format!(
+            "&algorithm={}&digits={}&{}",
+            self.algorithm.name(),
+            self.digits,
+            moving_factor
+        ));
+        uri
+    }
+}
+
+fn parse_number<T: core::str::FromStr>(value: &str) -> Result<T, OtpauthError> {
+    value.parse().map_err(|_| OtpauthError::InvalidParameter)
+}
+
+/// Decode the "%XX" sequences of a part of the URI.
+fn decode(value: &str) -> Result<String, OtpauthError> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut position = 0;
+    while position < bytes.len() {
+        if bytes[position] == b'%' {
+            let digits = bytes
+                .get(position + 1..position + 3)
+                .filter(|digits| digits.iter().all(u8::is_ascii_hexdigit))
+                .ok_or(OtpauthError::InvalidEncoding)?;
+            decoded.push((hex_value(digits[0]) << 4) | hex_value(digits[1]));
+            position += 3;
+        } else {
+            decoded.push(bytes[position]);
+            position += 1;
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| OtpauthError::InvalidEncoding)
+}
+
+fn hex_value(digit: u8) -> u8 {
+    match digit {
+        b'0'..=b'9' => digit - b'0',
+        b'a'..=b'f' => digit - b'a' + 10,
+        _ => digit - b'A' + 10,
+    }
+}
+
+/// Percent-encode everything but the unreserved characters and "@".
+fn encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~@".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_import_totp_uri() {
+        let entry = SecretEntry::from_otpauth_uri(
+            "otpauth://totp/Example:alice%40google.com?secret=JBSWY3DPEHPK3PXP&issuer=Example&digits=8",
+        )
+        .unwrap();
+
+        assert_eq!(
+            entry,
+            SecretEntry {
+                issuer: String::from("Example"),
+                account_label: String::from("alice@google.com"),
+                digits: 8,
+                ..SecretEntry::new("Example:alice@google.com", "JBSWY3DPEHPK3PXP")
+            }
+        );
+    }
+
+    #[test]
+    fn should_import_hotp_uri() {
+        let entry = SecretEntry::from_otpauth_uri(
+            "otpauth://hotp/alice?secret=JBSWY3DPEHPK3PXP&algorithm=SHA256&counter=42",
+        )
+        .unwrap();
+
+        assert_eq!(entry.id, "alice");
+        assert_eq!(entry.kind, OtpKind::Hotp);
+        assert_eq!(entry.algorithm, Algorithm::Sha256);
+        assert_eq!(entry.counter, 42);
+        assert_eq!(
+            SecretEntry::from_otpauth_uri("otpauth://hotp/alice?secret=JBSWY3DPEHPK3PXP"),
+            Err(OtpauthError::MissingCounter)
+        );
+    }
+
+    #[test]
+    fn should_export_and_import_same_entry() {
+        let entry = SecretEntry {
+            issuer: String::from("Big Corp"),
+            account_label: String::from("alice@example.com"),
+            ..SecretEntry::new("Big Corp:alice@example.com", "JBSWY3DPEHPK3PXP")
+        };
+        let uri = entry.to_otpauth_uri();
+
+        assert_eq!(
+            uri,
+            "otpauth://totp/Big%20Corp:alice@example.com?secret=JBSWY3DPEHPK3PXP&issuer=Big%20Corp&algorithm=SHA1&digits=6&period=30"
+        );
+        assert_eq!(SecretEntry::from_otpauth_uri(&uri), Ok(entry));
+    }
+
+    #[test]
+    fn should_reject_invalid_uris() {
+        assert_eq!(
+            SecretEntry::from_otpauth_uri("https://example.com"),
+            Err(OtpauthError::NotAnOtpauthUri)
+        );
+        assert_eq!(
+            SecretEntry::from_otpauth_uri("otpauth://motp/alice?secret=A"),
+            Err(OtpauthError::UnknownKind)
+        );
+        assert_eq!(
+            SecretEntry::from_otpauth_uri("otpauth://totp/alice?issuer=Example"),
+            Err(OtpauthError::MissingSecret)
+        );
+        assert_eq!(
+            SecretEntry::from_otpauth_uri("otpauth://totp/alice?secret=A&digits=six"),
+            Err(OtpauthError::InvalidParameter)
+        );
+        assert_eq!(
+            SecretEntry::from_otpauth_uri("otpauth://totp/alice%2?secret=A"),
+            Err(OtpauthError::InvalidEncoding)
+        );
+    }
+}
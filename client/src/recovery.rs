@@ -0,0 +1,149 @@
+use alloc::vec::Vec;
+
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped,
+};
+
+/// Guardians of a shared list, who can give it to a new account when its owner loses its key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Recovery {
+    /// Owner who chose the guardians, it loses its role when the list is recovered
+    pub owner: AccountHash,
+    pub guardians: Vec<AccountHash>,
+    /// Number of guardians who must approve the same new owner
+    pub threshold: u32,
+    /// Time in milliseconds between the last approval and the recovery, so the owner can cancel
+    /// a recovery it didn't ask for
+    pub timelock: u64,
+    pub request: Option<RecoveryRequest>,
+}
+
+/// Recovery approved by some of the guardians.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecoveryRequest {
+    /// Account that becomes the owner of the list
+    pub new_owner: AccountHash,
+    /// Guardians who approved the new owner, in the order of their approval
+    pub approvals: Vec<AccountHash>,
+    /// Block time when the threshold was reached, None before
+    pub approved_at: Option<u64>,
+}
+
+impl Recovery {
+    /// Block time from which the recovery can be completed, None while the threshold is not
+    /// reached.
+    pub fn unlocked_at(&self) -> Option<u64> {
+        self.request
+            .as_ref()
+            .and_then(|request| request.approved_at)
+            .map(|approved_at| approved_at.saturating_add(self.timelock))
+    }
+}
+
+impl CLTyped for Recovery {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for Recovery {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.owner.to_bytes()?);
+        result.append(&mut self.guardians.to_bytes()?);
+        result.append(&mut self.threshold.to_bytes()?);
+        result.append(&mut self.timelock.to_bytes()?);
+        result.append(&mut self.request.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.owner.serialized_length()
+            + self.guardians.serialized_length()
+            + self.threshold.serialized_length()
+            + self.timelock.serialized_length()
+            + self.request.serialized_length()
+    }
+}
+
+impl FromBytes for Recovery {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (owner, remainder) = AccountHash::from_bytes(bytes)?;
+        let (guardians, remainder) = Vec::<AccountHash>::from_bytes(remainder)?;
+        let (threshold, remainder) = u32::from_bytes(remainder)?;
+        let (timelock, remainder) = u64::from_bytes(remainder)?;
+        let (request, remainder) = Option::<RecoveryRequest>::from_bytes(remainder)?;
+        let recovery = Recovery {
+            owner,
+            guardians,
+            threshold,
+            timelock,
+            request,
+        };
+        Ok((recovery, remainder))
+    }
+}
+
+impl CLTyped for RecoveryRequest {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for RecoveryRequest {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.new_owner.to_bytes()?);
+        result.append(&mut self.approvals.to_bytes()?);
+        result.append(&mut self.approved_at.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.new_owner.serialized_length()
+            + self.approvals.serialized_length()
+            + self.approved_at.serialized_length()
+    }
+}
+
+impl FromBytes for RecoveryRequest {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (new_owner, remainder) = AccountHash::from_bytes(bytes)?;
+        let (approvals, remainder) = Vec::<AccountHash>::from_bytes(remainder)?;
+        let (approved_at, remainder) = Option::<u64>::from_bytes(remainder)?;
+        let request = RecoveryRequest {
+            new_owner,
+            approvals,
+            approved_at,
+        };
+        Ok((request, remainder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn should_serialize_recovery() {
+        let recovery = Recovery {
+            owner: AccountHash::new([1u8; 32]),
+            guardians: vec![AccountHash::new([2u8; 32]), AccountHash::new([3u8; 32])],
+            threshold: 2,
+            timelock: 86_400_000,
+            request: Some(RecoveryRequest {
+                new_owner: AccountHash::new([4u8; 32]),
+                approvals: vec![AccountHash::new([2u8; 32]), AccountHash::new([3u8; 32])],
+                approved_at: Some(1_000),
+            }),
+        };
+        let bytes = recovery.to_bytes().unwrap();
+
+        assert_eq!(bytes.len(), recovery.serialized_length());
+        assert_eq!(bytesrepr::deserialize::<Recovery>(bytes).unwrap(), recovery);
+        assert_eq!(recovery.unlocked_at(), Some(86_401_000));
+    }
+}
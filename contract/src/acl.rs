@@ -0,0 +1,193 @@
+use alloc::format;
+use alloc::string::String;
+
+use blockchain_authenticator_client::{Method, Role};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{account::AccountHash, URef};
+use core::convert::TryInto;
+
+use crate::error::Error;
+
+//Suffix of the named key of the dictionary holding the roles of the accounts on a shared list
+pub const ACL_SUFFIX: &str = "_acl";
+//Named key of the stored contract. The lists created while it exists are owned by the contract package.
+pub const SHARED_KEY_NAME: &str = "blockchain_authenticator_shared";
+
+/**
+ * pub fn acl_key_name - Get the named key of the roles of a list
+ *
+ * @param  {type} named_key: &str Name of the list
+ * @return {type}                 The named key of the dictionary [named-key]_acl
+ */
+pub fn acl_key_name(named_key: &str) -> String {
+    format!("{}{}", named_key, ACL_SUFFIX)
+}
+
+/**
+ * fn account_item - Get the key of an account in the dictionary of the roles
+ *
+ * @param  {type} account: AccountHash Account
+ * @return {type}                      The hash of the account in hexadecimal, 64 characters
+ */
+fn account_item(account: AccountHash) -> String {
+    account
+        .value()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/**
+ * pub fn is_shared - Check if a list is shared, its roles are then checked on each call
+ *
+ * @param  {type} named_key: &str Name of the list
+ * @return {type}                 true if the list has a dictionary of roles
+ */
+pub fn is_shared(named_key: &str) -> bool {
+    runtime::has_key(&acl_key_name(named_key))
+}
+
+/**
+ * fn acl_uref - Get the dictionary of the roles of a list
+ *
+ * @param  {type} named_key: &str Name of the list
+ * @return {type}                 The dictionary, or revert if the list is not shared
+ */
+fn acl_uref(named_key: &str) -> URef {
+    match runtime::get_key(&acl_key_name(named_key)) {
+        Some(key) => key.try_into().unwrap_or_revert(),
+        None if runtime::has_key(named_key) => runtime::revert(Error::NotShared),
+        None => runtime::revert(Error::MissingList),
+    }
+}
+
+/**
+ * pub fn create_if_shared - Create the roles of a new list when it is created by the stored contract,
+ * the caller becomes its owner. A list created by the session code stays in the named keys of the caller.
+ *
+ * @param  {type} named_key: &str Name of the new list
+ * @return {type}                 No return value
+ */
+pub fn create_if_shared(named_key: &str) {
+    if runtime::has_key(SHARED_KEY_NAME) {
+        let acl = storage::new_dictionary(&acl_key_name(named_key)).unwrap_or_revert();
+        storage::dictionary_put(acl, &account_item(runtime::get_caller()), Some(Role::Owner));
+    }
+}
+
+/**
+ * pub fn role - Get the role of an account on a shared list
+ *
+ * @param  {type} account: AccountHash Account
+ * @return {type}                      The role, None if the account has no role
+ */
+pub fn role(named_key: &str, account: AccountHash) -> Option<Role> {
+    storage::dictionary_get::<Option<Role>>(acl_uref(named_key), &account_item(account))
+        .unwrap_or_revert()
+        .flatten()
+}
+
+/**
+ * pub fn has_role - Check if the caller can run a method on a list
+ *
+ * @param  {type} required: Role Role needed by the method
+ * @return {type}                true if the list is not shared, or if the role of the caller includes the required role
+ */
+pub fn has_role(named_key: &str, required: Role) -> bool {
+    if !is_shared(named_key) {
+        return true;
+    }
+    match role(named_key, runtime::get_caller()) {
+        Some(role) => role >= required,
+        None => false,
+    }
+}
+
+/**
+ * pub fn check - Revert if the caller cant run a method on a list
+ *
+ * @param  {type} required: Role Role needed by the method
+ * @return {type}                No return value
+ */
+pub fn check(named_key: &str, required: Role) {
+    if !has_role(named_key, required) {
+        runtime::revert(Error::Forbidden);
+    }
+}
+
+/**
+ * pub fn required_role - Get the role needed by a method on a shared list
+ *
+ * @param  {type} method: Method Method to run
+ * @return {type}                The role, None for the methods of the guardians and of the beneficiary, which are checked
+ *                                by the recovery and by the inheritance
+ */
+pub fn required_role(method: Method) -> Option<Role> {
+    match method {
+        //"copy" reads the list, the role on the other list is checked by copy_list
+        Method::Get | Method::Lists | Method::Copy => Some(Role::Reader),
+        Method::Configure
+        | Method::Rename
+        | Method::DelList
+        | Method::Grant
+        | Method::Revoke
+        | Method::AddDevice
+        | Method::DelDevice
+        | Method::SetGuardians
+        | Method::CancelRecovery
+        | Method::SetBeneficiary
+        | Method::Heartbeat => Some(Role::Owner),
+        Method::ApproveRecovery | Method::CompleteRecovery | Method::Claim => None,
+        _ => Some(Role::Writer),
+    }
+}
+
+/**
+ * pub fn grant - Give a role on a shared list to an account, or change its role
+ *
+ * @param  {type} account: AccountHash Account
+ * @param  {type} role: Role           New role of the account
+ * @return {type}                      No return value
+ */
+pub fn grant(named_key: &str, account: AccountHash, role: Role) {
+    put_role(named_key, account, Some(role));
+}
+
+/**
+ * pub fn revoke - Remove the role of an account on a shared list
+ *
+ * @param  {type} account: AccountHash Account
+ * @return {type}                      No return value
+ */
+pub fn revoke(named_key: &str, account: AccountHash) {
+    put_role(named_key, account, None);
+}
+
+/**
+ * fn put_role - Save the role of an account. An owner cant change its own role, so a list always keeps an owner.
+ *
+ * @param  {type} account: AccountHash Account
+ * @param  {type} role: Option<Role>   New role of the account, None to remove it
+ * @return {type}                      No return value
+ */
+fn put_role(named_key: &str, account: AccountHash, role: Option<Role>) {
+    if account == runtime::get_caller() {
+        runtime::revert(Error::Forbidden);
+    }
+    set_role(named_key, account, role);
+}
+
+/**
+ * pub fn set_role - Save the role of an account without checking the caller, used by the recovery and the inheritance
+ * of a list
+ *
+ * @param  {type} account: AccountHash Account
+ * @param  {type} role: Option<Role>   New role of the account, None to remove it
+ * @return {type}                      No return value
+ */
+pub fn set_role(named_key: &str, account: AccountHash, role: Option<Role>) {
+    storage::dictionary_put(acl_uref(named_key), &account_item(account), role);
+}
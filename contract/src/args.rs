@@ -0,0 +1,58 @@
+use alloc::vec::Vec;
+
+use casper_contract::{
+    contract_api::{self, runtime},
+    ext_ffi,
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{api_error, bytesrepr, bytesrepr::FromBytes, ApiError, CLTyped};
+
+/**
+ * fn get_named_arg_size - Get the size of a named argument of the deploy
+ *
+ * @param  {type} name: &str Name of the argument
+ * @return {type}            The size of the argument, or None if the argument is missing
+ */
+fn get_named_arg_size(name: &str) -> Option<usize> {
+    let mut arg_size: usize = 0;
+    let ret = unsafe {
+        ext_ffi::casper_get_named_arg_size(
+            name.as_bytes().as_ptr(),
+            name.len(),
+            &mut arg_size as *mut usize,
+        )
+    };
+    match api_error::result_from(ret) {
+        Ok(_) => Some(arg_size),
+        Err(ApiError::MissingArgument) => None,
+        Err(e) => runtime::revert(e),
+    }
+}
+
+/**
+ * pub fn get_optional_named_arg - Get a named argument that the caller may omit.
+ * runtime::get_named_arg reverts with ApiError::MissingArgument when the argument is missing.
+ *
+ * @param  {type} name: &str Name of the argument
+ * @return {type}            The value of the argument, or None if the argument is missing
+ */
+pub fn get_optional_named_arg<T: CLTyped + FromBytes>(name: &str) -> Option<T> {
+    let arg_size = get_named_arg_size(name)?;
+    let arg_bytes = if arg_size > 0 {
+        let data_non_null_ptr = contract_api::alloc_bytes(arg_size);
+        let ret = unsafe {
+            ext_ffi::casper_get_named_arg(
+                name.as_bytes().as_ptr(),
+                name.len(),
+                data_non_null_ptr.as_ptr(),
+                arg_size,
+            )
+        };
+        let data = unsafe { Vec::from_raw_parts(data_non_null_ptr.as_ptr(), arg_size, arg_size) };
+        api_error::result_from(ret).unwrap_or_revert();
+        data
+    } else {
+        Vec::new()
+    };
+    Some(bytesrepr::deserialize(arg_bytes).unwrap_or_revert_with(ApiError::InvalidArgument))
+}
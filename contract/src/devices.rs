@@ -0,0 +1,154 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use blockchain_authenticator_client::{Device, DeviceScope, Method};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{PublicKey, URef};
+use core::convert::TryInto;
+
+use crate::error::Error;
+
+//Suffix of the named key of the URef holding the devices allowed to call a list
+pub const DEVICES_SUFFIX: &str = "_devices";
+
+/**
+ * pub fn devices_key_name - Get the named key of the devices of a list
+ *
+ * @param  {type} named_key: &str Name of the list
+ * @return {type}                 The named key of the URef [named-key]_devices
+ */
+pub fn devices_key_name(named_key: &str) -> String {
+    format!("{}{}", named_key, DEVICES_SUFFIX)
+}
+
+/**
+ * pub fn devices - Get the devices allowed to call a list
+ *
+ * @param  {type} named_key: &str Name of the list
+ * @return {type}                 The devices, in the order they were added. Empty if any key of the account can call the list.
+ */
+pub fn devices(named_key: &str) -> Vec<Device> {
+    match runtime::get_key(&devices_key_name(named_key)) {
+        Some(key) => {
+            let uref: URef = key.try_into().unwrap_or_revert();
+            storage::read(uref).unwrap_or_revert().unwrap_or_default()
+        }
+        None => Vec::new(),
+    }
+}
+
+/**
+ * fn write_devices - Save the devices of a list, the URef is created on the first device
+ *
+ * @param  {type} devices: Vec<Device> Devices of the list
+ * @return {type}                      No return value
+ */
+fn write_devices(named_key: &str, devices: Vec<Device>) {
+    match runtime::get_key(&devices_key_name(named_key)) {
+        Some(key) => {
+            let uref: URef = key.try_into().unwrap_or_revert();
+            storage::write(uref, devices);
+        }
+        None => runtime::put_key(
+            &devices_key_name(named_key),
+            storage::new_uref(devices).into(),
+        ),
+    }
+}
+
+/**
+ * pub fn check - Revert if the deploy is not signed by a device allowed to run a method on a list.
+ * A list without device can be called with any key of the account.
+ * Only a shared list is protected : the lists in the named keys of an account can be changed by any key
+ * of the account with another session code, which doesnt call this function.
+ *
+ * @param  {type} required: DeviceScope Scope needed by the method
+ * @return {type}                       No return value
+ */
+pub fn check(named_key: &str, required: DeviceScope) {
+    let devices = devices(named_key);
+    if devices.is_empty() {
+        return;
+    }
+    let authorization_keys = runtime::list_authorization_keys();
+    if !devices.iter().any(|device| {
+        device.scope >= required && authorization_keys.contains(&device.account_hash())
+    }) {
+        runtime::revert(Error::DeviceNotAllowed);
+    }
+}
+
+/**
+ * pub fn required_scope - Get the scope needed by a method
+ *
+ * @param  {type} method: Method Method to run
+ * @return {type}                The scope, None for the methods of the guardians and of the beneficiary, which sign
+ *                                with their own accounts
+ */
+pub fn required_scope(method: Method) -> Option<DeviceScope> {
+    match method {
+        //"copy" reads the list, the scope on the other list is checked by copy_list
+        //A heartbeat doesnt change the list, any device of the owner can send it
+        Method::Get | Method::Lists | Method::Copy | Method::Heartbeat => {
+            Some(DeviceScope::ReadOnly)
+        }
+        //"add" replaces the elements having the same ID, "insert" refuses them
+        //The operations of a batch that dont add a new element are checked by apply_operation
+        Method::Insert | Method::Batch => Some(DeviceScope::AddOnly),
+        Method::ApproveRecovery | Method::CompleteRecovery | Method::Claim => None,
+        _ => Some(DeviceScope::Full),
+    }
+}
+
+/**
+ * pub fn add_device - Allow a key to call a list, or change the label and the scope of a device
+ *
+ * @param  {type} device: Device Device to allow
+ * @return {type}                No return value
+ */
+pub fn add_device(named_key: &str, device: Device) {
+    if !runtime::has_key(named_key) {
+        runtime::revert(Error::MissingList);
+    }
+    let mut devices = devices(named_key);
+    match devices
+        .iter_mut()
+        .find(|existing| existing.public_key == device.public_key)
+    {
+        Some(existing) => *existing = device,
+        None => devices.push(device),
+    }
+    write_devices(named_key, devices);
+}
+
+/**
+ * pub fn clear - Remove all the devices of a list, any key of the account can call the list again
+ *
+ * @return {type}  No return value
+ */
+pub fn clear(named_key: &str) {
+    if runtime::has_key(&devices_key_name(named_key)) {
+        write_devices(named_key, Vec::new());
+    }
+}
+
+/**
+ * pub fn remove_device - Remove a key from the devices of a list. Once the last device is removed,
+ * any key of the account can call the list again.
+ *
+ * @param  {type} public_key: PublicKey Key of the device
+ * @return {type}                       No return value
+ */
+pub fn remove_device(named_key: &str, public_key: PublicKey) {
+    let mut devices = devices(named_key);
+    let length = devices.len();
+    devices.retain(|device| device.public_key != public_key);
+    if devices.len() == length {
+        runtime::revert(Error::UnknownDevice);
+    }
+    write_devices(named_key, devices);
+}
@@ -0,0 +1,75 @@
+use casper_types::ApiError;
+
+/**
+ * pub enum Error - Errors returned by the contract. Each error reverts the deploy with ApiError::User([code])
+ */
+#[repr(u16)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    //The "method" parameter is not one of the known methods
+    UnknownMethod = 1,
+    //An element of "keys" is not formatted as "[ID];[VALUE]", or an element of "entries" has a ";" in its ID or an empty secret
+    MalformedEntry = 2,
+    //An element of "keys" has an empty ID
+    EmptyId = 3,
+    //The same ID is used twice in "keys"
+    DuplicateId = 4,
+    //The named key doesnt exist
+    MissingList = 5,
+    //An ID is longer than MAX_ID_LENGTH characters
+    IdTooLong = 6,
+    //The list was created by a previous version of the contract, it must be migrated with the "migrate" method
+    LegacyList = 7,
+    //The named key ends with a suffix used by the contract for its own named keys, or is the named key of the registry
+    ReservedName = 8,
+    //The list only accepts encrypted elements and the VALUE of an element is not an envelope
+    PlaintextEntry = 9,
+    //The VALUE of an element starts with a version prefix but is not a valid envelope
+    MalformedEnvelope = 10,
+    //An element of "entries" has a number of digits or a period that can't generate a password
+    InvalidEntry = 11,
+    //An element of "uris" is not a valid otpauth URI
+    InvalidUri = 12,
+    //The "id" parameter is not the ID of an element of the list
+    MissingEntry = 13,
+    //The counter of a time-based element can't be incremented
+    NotCounterBased = 14,
+    //The new name of a list is already used by a named key
+    ListExists = 15,
+    //The list was modified since the "expected-version" was read
+    VersionConflict = 16,
+    //A trashed element given to "purge" was removed less than the retention period ago
+    RetentionNotElapsed = 17,
+    //The role of the caller on a shared list doesnt allow the method, or an owner tried to change its own role
+    Forbidden = 18,
+    //"grant" and "revoke" were called on a list that is not shared by the stored contract
+    NotShared = 19,
+    //The list has devices and the deploy is not signed by a device whose scope allows the method
+    DeviceNotAllowed = 20,
+    //The key of "deldevice" is not a device of the list
+    UnknownDevice = 21,
+    //The caller of "approverecovery" is not a guardian of the list
+    NotGuardian = 22,
+    //The threshold of "setguardians" is 0 or higher than the number of guardians, or a guardian is given twice
+    InvalidGuardians = 23,
+    //A guardian approved another new owner than the pending recovery
+    RecoveryConflict = 24,
+    //"completerecovery" was called before the threshold was reached or before the end of the timelock
+    RecoveryNotReady = 25,
+    //The caller of "claim" is not the beneficiary of the list
+    NotBeneficiary = 26,
+    //"claim" was called before the end of the inactivity period of the owner
+    OwnerActive = 27,
+    //A tag is empty, or a tag or a folder has an empty segment ("work//servers")
+    InvalidTag = 28,
+    //The IDs of "reorder" are not the IDs of all the elements of the list
+    InvalidOrder = 29,
+    //An encrypted element is renamed by "batch" without its secret sealed with the new ID
+    SealedEntry = 30,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
@@ -0,0 +1,84 @@
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+
+use blockchain_authenticator_client::ChangeKind;
+use casper_contract::contract_api::storage;
+
+//Prefix of the type of the events, so the indexers can tell them from the events of other contracts
+const EVENT_TYPE_PREFIX: &str = "blockchain_authenticator_";
+
+/**
+ * pub enum ListEvent - A change of a list, written as an event record for the indexers
+ */
+pub enum ListEvent<'a> {
+    //An element changed, with the same kinds of change as the history
+    Element { kind: ChangeKind, id: &'a str },
+    //All the elements were removed by "delall"
+    Cleared,
+    //The elements were put in a new order by "reorder"
+    Reordered,
+    //The list was renamed by "rename", the event is written with the new name
+    Renamed { previous_named_key: &'a str },
+    //The settings of the list were changed by "configure"
+    Configured,
+    //The list was converted to the current format by "migrate"
+    Migrated,
+}
+
+impl<'a> ListEvent<'a> {
+    fn event_type(&self) -> &'static str {
+        match self {
+            ListEvent::Element { kind, .. } => match kind {
+                ChangeKind::Added => "added",
+                ChangeKind::Updated => "updated",
+                ChangeKind::Deleted => "deleted",
+                ChangeKind::Restored => "restored",
+                ChangeKind::Purged => "purged",
+                ChangeKind::Renamed => "renamed",
+            },
+            ListEvent::Cleared => "cleared",
+            ListEvent::Reordered => "reordered",
+            ListEvent::Renamed { .. } => "renamed",
+            ListEvent::Configured => "configured",
+            ListEvent::Migrated => "migrated",
+        }
+    }
+}
+
+/**
+ * pub fn emit - Write an event record, following the event pattern of the CEP-47 contracts :
+ * each event is a new URef holding a map of strings, so it appears in the effects of the deploy.
+ *
+ * event_type => blockchain_authenticator_[added|updated|deleted|restored|purged|cleared|reordered|renamed|configured|migrated]
+ * named_key => name of the list
+ * id => ID of the element, only set for the changes of an element
+ * previous_named_key => previous name of the list, only set for "renamed"
+ * version => version of the list after the call
+ *
+ * @param  {type} named_key: &str   Name of the list
+ * @param  {type} version: u64      Version of the list after the call
+ * @param  {type} event: ListEvent  Change of the list
+ * @return {type}                   No return value
+ */
+pub fn emit(named_key: &str, version: u64, event: ListEvent) {
+    let mut record: BTreeMap<String, String> = BTreeMap::new();
+    record.insert(
+        String::from("event_type"),
+        [EVENT_TYPE_PREFIX, event.event_type()].concat(),
+    );
+    record.insert(String::from("named_key"), String::from(named_key));
+    match event {
+        ListEvent::Element { id, .. } => {
+            record.insert(String::from("id"), String::from(id));
+        }
+        ListEvent::Renamed { previous_named_key } => {
+            record.insert(
+                String::from("previous_named_key"),
+                String::from(previous_named_key),
+            );
+        }
+        _ => {}
+    }
+    record.insert(String::from("version"), version.to_string());
+    storage::new_uref(record);
+}
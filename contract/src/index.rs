@@ -0,0 +1,229 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use casper_contract::{contract_api::storage, unwrap_or_revert::UnwrapOrRevert};
+use casper_types::URef;
+
+const LENGTH_ITEM: &str = "length";
+const FIRST_ITEM: &str = "first";
+const LAST_ITEM: &str = "last";
+const NODE_PREFIX: &str = "node:";
+
+/**
+ * pub struct Index - Ordered set of IDs stored in a dictionary, so that the IDs can be enumerated and an ID
+ * can be added, removed or renamed without reading or moving the other IDs.
+ *
+ * The IDs are linked to each other, the items of the dictionary are prefixed by the namespace of the index :
+ * [namespace]length => number of IDs
+ * [namespace]first => first ID, "" if the index is empty
+ * [namespace]last => last ID, "" if the index is empty
+ * [namespace]node:[ID] => Some((previous ID, next ID)), "" before the first ID and after the last ID.
+ *                         None once the ID is removed.
+ */
+pub struct Index {
+    dictionary: URef,
+    namespace: &'static str,
+}
+
+impl Index {
+    pub fn new(dictionary: URef, namespace: &'static str) -> Self {
+        Index {
+            dictionary,
+            namespace,
+        }
+    }
+
+    /**
+     * pub const fn id_prefix_length - Get the length of the prefix added to an ID in the keys of an index
+     *
+     * @param  {type} namespace: &str Namespace of the index
+     * @return {type}                 Length of "[namespace]node:"
+     */
+    pub const fn id_prefix_length(namespace: &str) -> usize {
+        namespace.len() + NODE_PREFIX.len()
+    }
+
+    fn item_key(&self, name: &str) -> String {
+        format!("{}{}", self.namespace, name)
+    }
+
+    fn node_key(&self, id: &str) -> String {
+        format!("{}{}{}", self.namespace, NODE_PREFIX, id)
+    }
+
+    /**
+     * pub fn len - Get the number of IDs
+     *
+     * @return {type}  Number of IDs
+     */
+    pub fn len(&self) -> u64 {
+        storage::dictionary_get(self.dictionary, &self.item_key(LENGTH_ITEM))
+            .unwrap_or_revert()
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn set_len(&self, length: u64) {
+        storage::dictionary_put(self.dictionary, &self.item_key(LENGTH_ITEM), length);
+    }
+
+    /**
+     * fn end - Get the first or the last ID
+     *
+     * @param  {type} item: &str FIRST_ITEM or LAST_ITEM
+     * @return {type}            The ID, "" if the index is empty
+     */
+    fn end(&self, item: &str) -> String {
+        storage::dictionary_get(self.dictionary, &self.item_key(item))
+            .unwrap_or_revert()
+            .unwrap_or_default()
+    }
+
+    fn set_end(&self, item: &str, id: &str) {
+        storage::dictionary_put(self.dictionary, &self.item_key(item), String::from(id));
+    }
+
+    /**
+     * fn node - Get the neighbours of an ID
+     *
+     * @param  {type} id: &str ID to look for
+     * @return {type}          The previous and the next IDs if the ID exists
+     */
+    fn node(&self, id: &str) -> Option<(String, String)> {
+        storage::dictionary_get::<Option<(String, String)>>(self.dictionary, &self.node_key(id))
+            .unwrap_or_revert()
+            .flatten()
+    }
+
+    fn set_node(&self, id: &str, node: Option<(String, String)>) {
+        storage::dictionary_put(self.dictionary, &self.node_key(id), node);
+    }
+
+    /**
+     * fn link - Make two IDs neighbours
+     *
+     * @param  {type} previous: &str ID before, "" if next becomes the first ID
+     * @param  {type} next: &str     ID after, "" if previous becomes the last ID
+     * @return {type}                No return value
+     */
+    fn link(&self, previous: &str, next: &str) {
+        match self.node(previous) {
+            Some((before, _)) => self.set_node(previous, Some((before, String::from(next)))),
+            None => self.set_end(FIRST_ITEM, next),
+        }
+        match self.node(next) {
+            Some((_, after)) => self.set_node(next, Some((String::from(previous), after))),
+            None => self.set_end(LAST_ITEM, previous),
+        }
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.node(id).is_some()
+    }
+
+    /**
+     * pub fn push - Add an ID at the end of the index if it doesnt exist
+     *
+     * @param  {type} id: &str ID to add
+     * @return {type}          true if the ID was added, false if it already existed
+     */
+    pub fn push(&self, id: &str) -> bool {
+        if self.contains(id) {
+            return false;
+        }
+        let last = self.end(LAST_ITEM);
+        self.set_node(id, Some((last.clone(), String::new())));
+        self.link(&last, id);
+        self.set_len(self.len() + 1);
+        true
+    }
+
+    /**
+     * pub fn remove - Remove an ID if it exists. Its neighbours are linked together, so the order
+     * of the other IDs is kept.
+     *
+     * @param  {type} id: &str ID to remove
+     * @return {type}          true if the ID was removed, false if it didnt exist
+     */
+    pub fn remove(&self, id: &str) -> bool {
+        let (previous, next) = match self.node(id) {
+            Some(node) => node,
+            None => return false,
+        };
+        self.set_node(id, None);
+        self.link(&previous, &next);
+        self.set_len(self.len() - 1);
+        true
+    }
+
+    /**
+     * pub fn rename - Replace an ID by another ID at the same position
+     *
+     * @param  {type} id: &str     ID to replace
+     * @param  {type} new_id: &str New ID, it must not be in the index
+     * @return {type}              true if the ID was replaced, false if it didnt exist
+     */
+    pub fn rename(&self, id: &str, new_id: &str) -> bool {
+        let (previous, next) = match self.node(id) {
+            Some(node) => node,
+            None => return false,
+        };
+        self.set_node(id, None);
+        self.set_node(new_id, Some((previous.clone(), next.clone())));
+        self.link(&previous, new_id);
+        self.link(new_id, &next);
+        true
+    }
+
+    /**
+     * pub fn reorder - Save the IDs in a new order
+     *
+     * @param  {type} ids: &[&str] All the IDs of the index, in their new order
+     * @return {type}              No return value
+     */
+    pub fn reorder(&self, ids: &[&str]) {
+        for (position, id) in ids.iter().enumerate() {
+            let previous = if position == 0 { "" } else { ids[position - 1] };
+            let next = ids.get(position + 1).copied().unwrap_or_default();
+            self.set_node(id, Some((String::from(previous), String::from(next))));
+        }
+        self.set_end(FIRST_ITEM, ids.first().copied().unwrap_or_default());
+        self.set_end(LAST_ITEM, ids.last().copied().unwrap_or_default());
+    }
+
+    /**
+     * pub fn ids - Get all the IDs
+     *
+     * @return {type}  The IDs, in their order
+     */
+    pub fn ids(&self) -> Vec<String> {
+        let mut ids = Vec::new();
+        let mut id = self.end(FIRST_ITEM);
+        while let Some((_, next)) = self.node(&id) {
+            ids.push(id);
+            id = next;
+        }
+        ids
+    }
+
+    /**
+     * pub fn clear - Remove all the IDs. The links between the IDs are erased, so the dictionary
+     * no longer holds any ID.
+     *
+     * @return {type}  The removed IDs
+     */
+    pub fn clear(&self) -> Vec<String> {
+        let ids = self.ids();
+        for id in ids.iter() {
+            self.set_node(id, None);
+        }
+        self.set_end(FIRST_ITEM, "");
+        self.set_end(LAST_ITEM, "");
+        self.set_len(0);
+        ids
+    }
+}
@@ -0,0 +1,129 @@
+use alloc::format;
+use alloc::string::String;
+
+use blockchain_authenticator_client::{Inheritance, Role};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{account::AccountHash, URef};
+use core::convert::TryInto;
+
+use crate::acl;
+use crate::devices;
+use crate::error::Error;
+
+//Suffix of the named key of the URef holding the beneficiary of a shared list
+pub const INHERITANCE_SUFFIX: &str = "_inheritance";
+
+/**
+ * pub fn inheritance_key_name - Get the named key of the beneficiary of a list
+ *
+ * @param  {type} named_key: &str Name of the list
+ * @return {type}                 The named key of the URef [named-key]_inheritance
+ */
+pub fn inheritance_key_name(named_key: &str) -> String {
+    format!("{}{}", named_key, INHERITANCE_SUFFIX)
+}
+
+/**
+ * fn read - Get the beneficiary of a list
+ *
+ * @param  {type} named_key: &str Name of the list
+ * @return {type}                 The beneficiary and the last activity of the owner, None if the owner didnt choose one
+ */
+fn read(named_key: &str) -> Option<Inheritance> {
+    let key = runtime::get_key(&inheritance_key_name(named_key))?;
+    let uref: URef = key.try_into().unwrap_or_revert();
+    storage::read(uref).unwrap_or_revert()
+}
+
+/**
+ * fn write - Save the beneficiary of a list, the URef is created on the first call
+ *
+ * @param  {type} inheritance: Inheritance The beneficiary and the last activity of the owner
+ * @return {type}                          No return value
+ */
+fn write(named_key: &str, inheritance: Inheritance) {
+    match runtime::get_key(&inheritance_key_name(named_key)) {
+        Some(key) => {
+            let uref: URef = key.try_into().unwrap_or_revert();
+            storage::write(uref, inheritance);
+        }
+        None => runtime::put_key(
+            &inheritance_key_name(named_key),
+            storage::new_uref(inheritance).into(),
+        ),
+    }
+}
+
+/**
+ * pub fn set_beneficiary - Choose the beneficiary of a shared list, the caller becomes the owner whose activity
+ * is tracked. The inactivity period starts now.
+ *
+ * @param  {type} beneficiary: AccountHash Account that can claim the list
+ * @param  {type} role: Role               Role given to the beneficiary by the claim
+ * @param  {type} inactivity: u64          Time in milliseconds without activity of the owner before the claim
+ * @return {type}                          No return value
+ */
+pub fn set_beneficiary(named_key: &str, beneficiary: AccountHash, role: Role, inactivity: u64) {
+    //The beneficiary cant take a list of another account, only the contract can hand over a shared list
+    if !acl::is_shared(named_key) {
+        runtime::revert(Error::NotShared);
+    }
+    let owner = runtime::get_caller();
+    if beneficiary == owner {
+        runtime::revert(Error::Forbidden);
+    }
+    write(
+        named_key,
+        Inheritance {
+            owner,
+            beneficiary,
+            role,
+            inactivity,
+            last_activity: u64::from(runtime::get_blocktime()),
+        },
+    );
+}
+
+/**
+ * pub fn record_activity - Move the last activity of the owner to the block time, the calls of the other
+ * accounts are ignored
+ *
+ * @return {type}  No return value
+ */
+pub fn record_activity(named_key: &str) {
+    if let Some(mut inheritance) = read(named_key) {
+        if inheritance.owner == runtime::get_caller() {
+            inheritance.last_activity = u64::from(runtime::get_blocktime());
+            write(named_key, inheritance);
+        }
+    }
+}
+
+/**
+ * pub fn claim - Give the role chosen by the owner to the beneficiary, once the owner is inactive.
+ * With the owner role, the previous owner loses its role and its devices are removed.
+ * The beneficiary is removed, the new owner can choose another one.
+ *
+ * @return {type}  No return value
+ */
+pub fn claim(named_key: &str) {
+    let inheritance = read(named_key).unwrap_or_revert_with(Error::NotBeneficiary);
+    if inheritance.beneficiary != runtime::get_caller() {
+        runtime::revert(Error::NotBeneficiary);
+    }
+    if u64::from(runtime::get_blocktime()) < inheritance.claimable_at() {
+        runtime::revert(Error::OwnerActive);
+    }
+    if inheritance.role == Role::Owner {
+        acl::set_role(named_key, inheritance.owner, None);
+        devices::clear(named_key);
+    }
+    //The claim never lowers a role the beneficiary already has
+    if acl::role(named_key, inheritance.beneficiary) < Some(inheritance.role) {
+        acl::set_role(named_key, inheritance.beneficiary, Some(inheritance.role));
+    }
+    runtime::remove_key(&inheritance_key_name(named_key));
+}
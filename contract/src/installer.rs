@@ -0,0 +1,458 @@
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+extern crate alloc;
+
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    contracts::NamedKeys, CLType, ContractPackageHash, EntryPoint, EntryPointAccess,
+    EntryPointType, EntryPoints, Parameter,
+};
+use contract::{
+    acl::SHARED_KEY_NAME, Error, Method, ACCOUNT_ARG_NAME, BENEFICIARY_ARG_NAME, COUNTER_ARG_NAME,
+    DATA_ARG_NAME, DEVICE_ARG_NAME, ENTRIES_ARG_NAME, EXPECTED_VERSION_ARG_NAME, FORCE_ARG_NAME,
+    GUARDIANS_ARG_NAME, ID_ARG_NAME, INACTIVITY_ARG_NAME, LABEL_ARG_NAME, METHOD_ARG_NAME,
+    NAMED_KEY_ARG_NAME, NEW_NAMED_KEY_ARG_NAME, NEW_OWNER_ARG_NAME, OPERATIONS_ARG_NAME,
+    PINNED_ARG_NAME, PURGE_ARG_NAME, RETENTION_ARG_NAME, ROLE_ARG_NAME, SCOPE_ARG_NAME,
+    SHARED_ENTRY_POINT, STRICT_ARG_NAME, TAG_ARG_NAME, THRESHOLD_ARG_NAME, TIMELOCK_ARG_NAME,
+    URIS_ARG_NAME,
+};
+
+const PACKAGE_HASH_KEY_NAME: &str = "blockchain_authenticator_package";
+const ACCESS_UREF_KEY_NAME: &str = "blockchain_authenticator_access";
+const CONTRACT_HASH_KEY_NAME: &str = "blockchain_authenticator";
+const CONTRACT_VERSION_KEY_NAME: &str = "blockchain_authenticator_version";
+
+#[no_mangle]
+pub extern "C" fn add() {
+    contract::execute(Method::Add);
+}
+
+#[no_mangle]
+pub extern "C" fn insert() {
+    contract::execute(Method::Insert);
+}
+
+#[no_mangle]
+pub extern "C" fn update() {
+    contract::execute(Method::Update);
+}
+
+#[no_mangle]
+pub extern "C" fn del() {
+    contract::execute(Method::Del);
+}
+
+#[no_mangle]
+pub extern "C" fn delall() {
+    contract::execute(Method::DelAll);
+}
+
+#[no_mangle]
+pub extern "C" fn restore() {
+    contract::execute(Method::Restore);
+}
+
+#[no_mangle]
+pub extern "C" fn purge() {
+    contract::execute(Method::Purge);
+}
+
+#[no_mangle]
+pub extern "C" fn migrate() {
+    contract::execute(Method::Migrate);
+}
+
+#[no_mangle]
+pub extern "C" fn get() {
+    contract::execute(Method::Get);
+}
+
+#[no_mangle]
+pub extern "C" fn configure() {
+    contract::execute(Method::Configure);
+}
+
+#[no_mangle]
+pub extern "C" fn increment() {
+    contract::execute(Method::Increment);
+}
+
+#[no_mangle]
+pub extern "C" fn resync() {
+    contract::execute(Method::Resync);
+}
+
+#[no_mangle]
+pub extern "C" fn lists() {
+    contract::execute(Method::Lists);
+}
+
+#[no_mangle]
+pub extern "C" fn rename() {
+    contract::execute(Method::Rename);
+}
+
+#[no_mangle]
+pub extern "C" fn copy() {
+    contract::execute(Method::Copy);
+}
+
+#[no_mangle]
+pub extern "C" fn dellist() {
+    contract::execute(Method::DelList);
+}
+
+#[no_mangle]
+pub extern "C" fn batch() {
+    contract::execute(Method::Batch);
+}
+
+#[no_mangle]
+pub extern "C" fn adddevice() {
+    contract::execute(Method::AddDevice);
+}
+
+#[no_mangle]
+pub extern "C" fn deldevice() {
+    contract::execute(Method::DelDevice);
+}
+
+#[no_mangle]
+pub extern "C" fn grant() {
+    contract::execute(Method::Grant);
+}
+
+#[no_mangle]
+pub extern "C" fn revoke() {
+    contract::execute(Method::Revoke);
+}
+
+#[no_mangle]
+pub extern "C" fn setguardians() {
+    contract::execute(Method::SetGuardians);
+}
+
+#[no_mangle]
+pub extern "C" fn approverecovery() {
+    contract::execute(Method::ApproveRecovery);
+}
+
+#[no_mangle]
+pub extern "C" fn cancelrecovery() {
+    contract::execute(Method::CancelRecovery);
+}
+
+#[no_mangle]
+pub extern "C" fn completerecovery() {
+    contract::execute(Method::CompleteRecovery);
+}
+
+#[no_mangle]
+pub extern "C" fn setbeneficiary() {
+    contract::execute(Method::SetBeneficiary);
+}
+
+#[no_mangle]
+pub extern "C" fn heartbeat() {
+    contract::execute(Method::Heartbeat);
+}
+
+#[no_mangle]
+pub extern "C" fn claim() {
+    contract::execute(Method::Claim);
+}
+
+#[no_mangle]
+pub extern "C" fn reorder() {
+    contract::execute(Method::Reorder);
+}
+
+#[no_mangle]
+pub extern "C" fn pin() {
+    contract::execute(Method::Pin);
+}
+
+#[no_mangle]
+pub extern "C" fn shared() {
+    let method: String = runtime::get_named_arg(METHOD_ARG_NAME);
+
+    contract::execute(
+        Method::from_name(method.as_str()).unwrap_or_revert_with(Error::UnknownMethod),
+    );
+}
+
+/**
+ * fn entry_point - Declare the entry point of a method on a list, with the named key and the optional expected version.
+ * The entry points are executed as session code, so the lists stay in the named keys of the caller.
+ *
+ * @param  {type} method: Method             Method called by the entry point
+ * @param  {type} params: Vec<Parameter>     Parameters of the method, in addition to the named key
+ * @param  {type} ret: CLType                Type of the returned value
+ * @return {type}                            The entry point
+ */
+fn entry_point(method: Method, params: Vec<Parameter>, ret: CLType) -> EntryPoint {
+    entry_point_of_type(method.name(), params, ret, EntryPointType::Session)
+}
+
+/**
+ * fn shared_entry_point - Declare the entry point of a method on a shared list.
+ * The entry points are executed in the context of the contract, so the lists are in the named keys of the contract.
+ *
+ * @param  {type} name: &str                 Name of the entry point
+ * @param  {type} params: Vec<Parameter>     Parameters of the method, in addition to the named key
+ * @param  {type} ret: CLType                Type of the returned value
+ * @return {type}                            The entry point
+ */
+fn shared_entry_point(name: &str, params: Vec<Parameter>, ret: CLType) -> EntryPoint {
+    entry_point_of_type(name, params, ret, EntryPointType::Contract)
+}
+
+fn entry_point_of_type(
+    name: &str,
+    mut params: Vec<Parameter>,
+    ret: CLType,
+    entry_point_type: EntryPointType,
+) -> EntryPoint {
+    params.insert(0, Parameter::new(NAMED_KEY_ARG_NAME, CLType::String));
+    params.push(Parameter::new(EXPECTED_VERSION_ARG_NAME, CLType::U64));
+    EntryPoint::new(
+        name,
+        params,
+        ret,
+        EntryPointAccess::Public,
+        entry_point_type,
+    )
+}
+
+/**
+ * fn entry_points - Declare the entry points of the stored contract, one for each method
+ *
+ * @return {type}  The entry points
+ */
+fn entry_points() -> EntryPoints {
+    let secret_codes_type = CLType::List(Box::new(CLType::String));
+    //SecretEntry has its own serialization, its CLType is Any
+    let entries_type = CLType::List(Box::new(CLType::Any));
+
+    let mut entry_points = EntryPoints::new();
+    //"insert" and "update" take the same parameters as "add"
+    for method in [Method::Add, Method::Insert, Method::Update].iter() {
+        entry_points.add_entry_point(entry_point(
+            *method,
+            vec![
+                Parameter::new(DATA_ARG_NAME, secret_codes_type.clone()),
+                Parameter::new(ENTRIES_ARG_NAME, entries_type.clone()),
+                Parameter::new(URIS_ARG_NAME, secret_codes_type.clone()),
+            ],
+            CLType::Unit,
+        ));
+    }
+    //"del", "restore", "purge" and "reorder" take IDs, "del" and "delall" can be filtered by a tag
+    entry_points.add_entry_point(entry_point(
+        Method::Del,
+        vec![
+            Parameter::new(DATA_ARG_NAME, secret_codes_type.clone()),
+            Parameter::new(TAG_ARG_NAME, CLType::String),
+        ],
+        CLType::Unit,
+    ));
+    for method in [Method::Restore, Method::Purge, Method::Reorder].iter() {
+        entry_points.add_entry_point(entry_point(
+            *method,
+            vec![Parameter::new(DATA_ARG_NAME, secret_codes_type.clone())],
+            CLType::Unit,
+        ));
+    }
+    entry_points.add_entry_point(entry_point(
+        Method::DelAll,
+        vec![
+            Parameter::new(FORCE_ARG_NAME, CLType::Bool),
+            Parameter::new(PURGE_ARG_NAME, CLType::Bool),
+            Parameter::new(TAG_ARG_NAME, CLType::String),
+        ],
+        CLType::Unit,
+    ));
+    entry_points.add_entry_point(entry_point(Method::Migrate, Vec::new(), CLType::Unit));
+    entry_points.add_entry_point(entry_point(Method::Get, Vec::new(), entries_type));
+    entry_points.add_entry_point(entry_point(
+        Method::Configure,
+        vec![
+            Parameter::new(STRICT_ARG_NAME, CLType::Bool),
+            Parameter::new(RETENTION_ARG_NAME, CLType::U64),
+        ],
+        CLType::Unit,
+    ));
+    entry_points.add_entry_point(entry_point(
+        Method::Increment,
+        vec![Parameter::new(ID_ARG_NAME, CLType::String)],
+        CLType::U64,
+    ));
+    entry_points.add_entry_point(entry_point(
+        Method::Resync,
+        vec![
+            Parameter::new(ID_ARG_NAME, CLType::String),
+            Parameter::new(COUNTER_ARG_NAME, CLType::U64),
+        ],
+        CLType::U64,
+    ));
+    entry_points.add_entry_point(entry_point(
+        Method::Pin,
+        vec![
+            Parameter::new(ID_ARG_NAME, CLType::String),
+            Parameter::new(PINNED_ARG_NAME, CLType::Bool),
+        ],
+        CLType::Unit,
+    ));
+    //The only entry point without a named key
+    entry_points.add_entry_point(EntryPoint::new(
+        Method::Lists.name(),
+        Vec::new(),
+        CLType::List(Box::new(CLType::String)),
+        EntryPointAccess::Public,
+        EntryPointType::Session,
+    ));
+    entry_points.add_entry_point(entry_point(
+        Method::Rename,
+        vec![Parameter::new(NEW_NAMED_KEY_ARG_NAME, CLType::String)],
+        CLType::Unit,
+    ));
+    entry_points.add_entry_point(entry_point(
+        Method::Copy,
+        vec![Parameter::new(NEW_NAMED_KEY_ARG_NAME, CLType::String)],
+        CLType::Unit,
+    ));
+    entry_points.add_entry_point(entry_point(Method::DelList, Vec::new(), CLType::Unit));
+    //Operation has its own serialization, its CLType is Any
+    entry_points.add_entry_point(entry_point(
+        Method::Batch,
+        vec![Parameter::new(
+            OPERATIONS_ARG_NAME,
+            CLType::List(Box::new(CLType::Any)),
+        )],
+        CLType::Unit,
+    ));
+    entry_points.add_entry_point(entry_point(
+        Method::AddDevice,
+        vec![
+            Parameter::new(DEVICE_ARG_NAME, CLType::PublicKey),
+            Parameter::new(LABEL_ARG_NAME, CLType::String),
+            Parameter::new(SCOPE_ARG_NAME, CLType::U8),
+        ],
+        CLType::Unit,
+    ));
+    entry_points.add_entry_point(entry_point(
+        Method::DelDevice,
+        vec![Parameter::new(DEVICE_ARG_NAME, CLType::PublicKey)],
+        CLType::Unit,
+    ));
+    //The roles can only be changed on the shared lists
+    entry_points.add_entry_point(shared_entry_point(
+        Method::Grant.name(),
+        vec![
+            Parameter::new(ACCOUNT_ARG_NAME, CLType::ByteArray(32)),
+            Parameter::new(ROLE_ARG_NAME, CLType::U8),
+        ],
+        CLType::Unit,
+    ));
+    entry_points.add_entry_point(shared_entry_point(
+        Method::Revoke.name(),
+        vec![Parameter::new(ACCOUNT_ARG_NAME, CLType::ByteArray(32))],
+        CLType::Unit,
+    ));
+    //Only a shared list can be handed over by the contract
+    entry_points.add_entry_point(shared_entry_point(
+        Method::SetGuardians.name(),
+        vec![
+            Parameter::new(
+                GUARDIANS_ARG_NAME,
+                CLType::List(Box::new(CLType::ByteArray(32))),
+            ),
+            Parameter::new(THRESHOLD_ARG_NAME, CLType::U32),
+            Parameter::new(TIMELOCK_ARG_NAME, CLType::U64),
+        ],
+        CLType::Unit,
+    ));
+    entry_points.add_entry_point(shared_entry_point(
+        Method::ApproveRecovery.name(),
+        vec![Parameter::new(NEW_OWNER_ARG_NAME, CLType::ByteArray(32))],
+        CLType::Unit,
+    ));
+    for method in [Method::CancelRecovery, Method::CompleteRecovery].iter() {
+        entry_points.add_entry_point(shared_entry_point(method.name(), Vec::new(), CLType::Unit));
+    }
+    entry_points.add_entry_point(shared_entry_point(
+        Method::SetBeneficiary.name(),
+        vec![
+            Parameter::new(BENEFICIARY_ARG_NAME, CLType::ByteArray(32)),
+            Parameter::new(ROLE_ARG_NAME, CLType::U8),
+            Parameter::new(INACTIVITY_ARG_NAME, CLType::U64),
+        ],
+        CLType::Unit,
+    ));
+    for method in [Method::Heartbeat, Method::Claim].iter() {
+        entry_points.add_entry_point(shared_entry_point(method.name(), Vec::new(), CLType::Unit));
+    }
+    //Any method on a shared list, with the arguments of the session code
+    entry_points.add_entry_point(shared_entry_point(
+        SHARED_ENTRY_POINT,
+        vec![Parameter::new(METHOD_ARG_NAME, CLType::String)],
+        CLType::Any,
+    ));
+    entry_points
+}
+
+/**
+ * Objective : Install the stored contract, or add a new version to the contract package if it is already installed
+ *
+ * The following named keys are saved in the account :
+ *
+ * blockchain_authenticator_package : hash of the contract package
+ * blockchain_authenticator_access : access URef needed to upgrade the contract package
+ * blockchain_authenticator : hash of the latest version of the contract
+ * blockchain_authenticator_version : number of the latest version of the contract
+ *
+ * The contract gets the named key blockchain_authenticator_shared, the lists created through its "shared" entry point
+ * are saved in the named keys of the contract and shared between accounts. The entry points of the roles, of the
+ * recovery and of the inheritance only act on these lists.
+ *
+ **/
+#[no_mangle]
+pub extern "C" fn call() {
+    //The lists created while this named key exists are shared lists
+    //A new version also keeps the named keys of the previous version, so the shared lists are kept
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(
+        String::from(SHARED_KEY_NAME),
+        storage::new_uref(true).into(),
+    );
+
+    let (contract_hash, contract_version) = match runtime::get_key(PACKAGE_HASH_KEY_NAME) {
+        //The contract package already exists, we add a new version
+        Some(package_key) => {
+            let package_hash = ContractPackageHash::new(package_key.into_hash().unwrap_or_revert());
+            storage::add_contract_version(package_hash, entry_points(), named_keys)
+        }
+        //Otherwise we create the contract package and its first version
+        None => storage::new_contract(
+            entry_points(),
+            Some(named_keys),
+            Some(String::from(PACKAGE_HASH_KEY_NAME)),
+            Some(String::from(ACCESS_UREF_KEY_NAME)),
+        ),
+    };
+
+    runtime::put_key(CONTRACT_HASH_KEY_NAME, contract_hash.into());
+    runtime::put_key(
+        CONTRACT_VERSION_KEY_NAME,
+        storage::new_uref(contract_version).into(),
+    );
+}
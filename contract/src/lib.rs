@@ -0,0 +1,250 @@
+#![no_std]
+
+//! Logic of the Blockchain Authenticator contract, shared by the session code (`contract.wasm`)
+//! and by the entry points of the stored contract (`installer.wasm`).
+
+extern crate alloc;
+
+pub mod acl;
+pub mod args;
+pub mod devices;
+pub mod error;
+pub mod events;
+pub mod index;
+pub mod inheritance;
+pub mod list;
+pub mod recovery;
+pub mod registry;
+pub mod secret_code;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use casper_types::{account::AccountHash, ApiError, CLValue};
+
+pub use error::Error;
+use list::WriteMode;
+use secret_code::SecretCode;
+
+//The names of the methods and of the arguments are shared with the clients
+pub use blockchain_authenticator_client::{
+    Device, DeviceScope, Method, Operation, Role, SecretEntry, ACCOUNT_ARG_NAME,
+    BENEFICIARY_ARG_NAME, COUNTER_ARG_NAME, DATA_ARG_NAME, DEVICE_ARG_NAME, ENTRIES_ARG_NAME,
+    EXPECTED_VERSION_ARG_NAME, FORCE_ARG_NAME, GUARDIANS_ARG_NAME, ID_ARG_NAME,
+    INACTIVITY_ARG_NAME, LABEL_ARG_NAME, METHOD_ARG_NAME, NAMED_KEY_ARG_NAME,
+    NEW_NAMED_KEY_ARG_NAME, NEW_OWNER_ARG_NAME, OPERATIONS_ARG_NAME, PINNED_ARG_NAME,
+    PURGE_ARG_NAME, RETENTION_ARG_NAME, ROLE_ARG_NAME, SCOPE_ARG_NAME, SHARED_ENTRY_POINT,
+    STRICT_ARG_NAME, TAG_ARG_NAME, THRESHOLD_ARG_NAME, TIMELOCK_ARG_NAME, URIS_ARG_NAME,
+};
+
+/**
+ * pub fn execute - Run a method with the named arguments of the deploy
+ *
+ * @param  {type} method: Method Method to run
+ * @return {type}                No return value, except for "get" which returns the elements of the list
+ *                                and "lists" which returns the names of the lists
+ */
+pub fn execute(method: Method) {
+    //We return the names of the lists, this method doesnt take a named key
+    //A shared list is only listed for the accounts having a role on it
+    if method == Method::Lists {
+        let names: Vec<String> = registry::names()
+            .into_iter()
+            .filter(|name| acl::has_role(name, Role::Reader))
+            .collect();
+        runtime::ret(CLValue::from_t(names).unwrap_or_revert())
+    }
+    let named_key: String = runtime::get_named_arg(NAMED_KEY_ARG_NAME);
+    //We refuse the call if the caller doesnt have the role needed by the method on a shared list
+    if let Some(role) = acl::required_role(method) {
+        acl::check(named_key.as_str(), role);
+    }
+    //We refuse the call if the deploy is not signed by a device allowed to run the method
+    if let Some(scope) = devices::required_scope(method) {
+        devices::check(named_key.as_str(), scope);
+    }
+    //We refuse the call if another call modified the list since the caller read it
+    //The version is only checked once the caller is allowed, so it cant be probed without a role
+    list::check_version(
+        named_key.as_str(),
+        args::get_optional_named_arg(EXPECTED_VERSION_ARG_NAME),
+    );
+
+    match method {
+        //We add the elements in parameter to the existing elements
+        //The elements can be given as "[ID];[VALUE]" strings, as SecretEntry, as otpauth URIs, or mixed
+        //"insert" refuses the existing IDs and "update" refuses the new IDs
+        Method::Add | Method::Insert | Method::Update => {
+            let values_to_add: Option<Vec<String>> = args::get_optional_named_arg(DATA_ARG_NAME);
+            let entries_to_add: Option<Vec<SecretEntry>> =
+                args::get_optional_named_arg(ENTRIES_ARG_NAME);
+            let uris_to_add: Option<Vec<String>> = args::get_optional_named_arg(URIS_ARG_NAME);
+            if values_to_add.is_none() && entries_to_add.is_none() && uris_to_add.is_none() {
+                runtime::revert(ApiError::MissingArgument);
+            }
+            let mut entries_to_add = entries_to_add.unwrap_or_default();
+            for uri in uris_to_add.unwrap_or_default().iter() {
+                entries_to_add.push(SecretCode::parse_uri(uri).unwrap_or_revert());
+            }
+            let mode = match method {
+                Method::Insert => WriteMode::Insert,
+                Method::Update => WriteMode::Update,
+                _ => WriteMode::Upsert,
+            };
+            //We create the named key if it doesnt exist, "update" needs an existing list
+            if mode != WriteMode::Update {
+                list::create_lists_if_not_exists(named_key.as_str());
+            }
+            list::create_or_update_secret_code_if_exists(
+                named_key.as_str(),
+                values_to_add.unwrap_or_default(),
+                entries_to_add,
+                mode,
+            );
+        }
+        //We move the existing elements to the trash, only the elements carrying the tag if one is given
+        Method::Del => list::remove_secret_code_if_exists(
+            named_key.as_str(),
+            runtime::get_named_arg(DATA_ARG_NAME),
+            args::get_optional_named_arg(TAG_ARG_NAME),
+        ),
+        //With a tag, the elements are always moved to the trash
+        Method::DelAll => match args::get_optional_named_arg(TAG_ARG_NAME) {
+            Some(tag) => list::remove_tagged_elements(named_key.as_str(), tag),
+            None => list::remove_all_elements(
+                named_key.as_str(),
+                args::get_optional_named_arg(FORCE_ARG_NAME).unwrap_or_default(),
+                args::get_optional_named_arg(PURGE_ARG_NAME).unwrap_or_default(),
+            ),
+        },
+        //We move the elements back from the trash, or erase them
+        Method::Restore => {
+            list::restore_secret_codes(named_key.as_str(), runtime::get_named_arg(DATA_ARG_NAME))
+        }
+        Method::Purge => list::purge_trash(
+            named_key.as_str(),
+            args::get_optional_named_arg(DATA_ARG_NAME),
+        ),
+        Method::Migrate => list::migrate_secret_codes(named_key.as_str()),
+        //We change the settings, a list can be configured before its first element is added
+        Method::Configure => {
+            list::create_lists_if_not_exists(named_key.as_str());
+            list::configure_list(
+                named_key.as_str(),
+                args::get_optional_named_arg(STRICT_ARG_NAME),
+                args::get_optional_named_arg(RETENTION_ARG_NAME),
+            );
+        }
+        //We move the counter of a counter-based element and return it to the caller
+        Method::Increment => {
+            let id: String = runtime::get_named_arg(ID_ARG_NAME);
+            let counter = list::increment_counter(named_key.as_str(), id.as_str());
+            runtime::ret(CLValue::from_t(counter).unwrap_or_revert())
+        }
+        Method::Resync => {
+            let id: String = runtime::get_named_arg(ID_ARG_NAME);
+            let counter = list::resync_counter(
+                named_key.as_str(),
+                id.as_str(),
+                runtime::get_named_arg(COUNTER_ARG_NAME),
+            );
+            runtime::ret(CLValue::from_t(counter).unwrap_or_revert())
+        }
+        //We change the order of the elements shown by the apps
+        Method::Reorder => {
+            list::reorder_elements(named_key.as_str(), runtime::get_named_arg(DATA_ARG_NAME))
+        }
+        Method::Pin => {
+            let id: String = runtime::get_named_arg(ID_ARG_NAME);
+            list::pin_element(
+                named_key.as_str(),
+                id.as_str(),
+                runtime::get_named_arg(PINNED_ARG_NAME),
+            );
+        }
+        //We return the elements to the caller, the pinned elements first
+        Method::Get => {
+            let secret_codes = list::read_secret_codes(named_key.as_str());
+            runtime::ret(CLValue::from_t(secret_codes).unwrap_or_revert())
+        }
+        //Handled above
+        Method::Lists => {}
+        //We move or copy the list to another named key
+        Method::Rename => {
+            let new_named_key: String = runtime::get_named_arg(NEW_NAMED_KEY_ARG_NAME);
+            list::rename_list(named_key.as_str(), new_named_key.as_str());
+        }
+        Method::Copy => {
+            let new_named_key: String = runtime::get_named_arg(NEW_NAMED_KEY_ARG_NAME);
+            list::copy_list(named_key.as_str(), new_named_key.as_str());
+        }
+        //We remove the list and its named keys
+        Method::DelList => list::delete_list(named_key.as_str()),
+        //We apply all the operations, or none of them if one is invalid
+        Method::Batch => {
+            let operations: Vec<Operation> = runtime::get_named_arg(OPERATIONS_ARG_NAME);
+            list::create_lists_if_not_exists(named_key.as_str());
+            list::apply_operations(named_key.as_str(), operations);
+        }
+        //We change the roles of the other accounts on a shared list
+        Method::Grant => acl::grant(
+            named_key.as_str(),
+            runtime::get_named_arg(ACCOUNT_ARG_NAME),
+            runtime::get_named_arg(ROLE_ARG_NAME),
+        ),
+        Method::Revoke => acl::revoke(named_key.as_str(), runtime::get_named_arg(ACCOUNT_ARG_NAME)),
+        //We change the devices allowed to call the list
+        Method::AddDevice => {
+            let label: String = runtime::get_named_arg(LABEL_ARG_NAME);
+            devices::add_device(
+                named_key.as_str(),
+                Device::new(
+                    runtime::get_named_arg(DEVICE_ARG_NAME),
+                    label.as_str(),
+                    runtime::get_named_arg(SCOPE_ARG_NAME),
+                ),
+            );
+        }
+        Method::DelDevice => {
+            devices::remove_device(named_key.as_str(), runtime::get_named_arg(DEVICE_ARG_NAME))
+        }
+        //We hand over a shared list to a new owner approved by its guardians
+        Method::SetGuardians => {
+            let guardians: Vec<AccountHash> = runtime::get_named_arg(GUARDIANS_ARG_NAME);
+            recovery::set_guardians(
+                named_key.as_str(),
+                guardians,
+                runtime::get_named_arg(THRESHOLD_ARG_NAME),
+                runtime::get_named_arg(TIMELOCK_ARG_NAME),
+            );
+        }
+        Method::ApproveRecovery => recovery::approve(
+            named_key.as_str(),
+            runtime::get_named_arg(NEW_OWNER_ARG_NAME),
+        ),
+        Method::CancelRecovery => recovery::cancel(named_key.as_str()),
+        Method::CompleteRecovery => recovery::complete(named_key.as_str()),
+        //We hand over a shared list to its beneficiary when the owner stops calling it
+        Method::SetBeneficiary => inheritance::set_beneficiary(
+            named_key.as_str(),
+            runtime::get_named_arg(BENEFICIARY_ARG_NAME),
+            runtime::get_named_arg(ROLE_ARG_NAME),
+            runtime::get_named_arg(INACTIVITY_ARG_NAME),
+        ),
+        //A heartbeat only records the activity of the owner, below
+        Method::Heartbeat => {}
+        Method::Claim => inheritance::claim(named_key.as_str()),
+    }
+
+    //The changes and the heartbeats of the owner delay the claim of the beneficiary
+    match method {
+        Method::Add
+        | Method::Insert
+        | Method::Update
+        | Method::Del
+        | Method::DelAll
+        | Method::Heartbeat => inheritance::record_activity(named_key.as_str()),
+        _ => {}
+    }
+}
@@ -0,0 +1,1242 @@
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use blockchain_authenticator_client::{
+    ChangeKind, ChangeRecord, DeviceScope, Operation, OtpKind, Role, SecretEntry, TrashedEntry,
+};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{bytesrepr::ToBytes, URef};
+use core::convert::TryInto;
+
+use crate::acl::{self, ACL_SUFFIX, SHARED_KEY_NAME};
+use crate::devices::{self, DEVICES_SUFFIX};
+use crate::error::Error;
+use crate::events::{self, ListEvent};
+use crate::index::Index;
+use crate::inheritance::{self, INHERITANCE_SUFFIX};
+use crate::recovery::{self, RECOVERY_SUFFIX};
+use crate::registry::{self, REGISTRY_KEY_NAME};
+use crate::secret_code::SecretCode;
+
+//Suffix of the named key of the dictionary holding the index of the IDs of a list
+pub const META_SUFFIX: &str = "_meta";
+//Suffix of the named key where a list created by a previous version of the contract is kept after its migration
+pub const LEGACY_SUFFIX: &str = "_legacy";
+//Namespace of the index of the IDs in the meta dictionary
+const IDS_NAMESPACE: &str = "ids:";
+//Item of the meta dictionary set to true when the list only accepts encrypted elements
+const STRICT_ITEM: &str = "strict";
+//Item of the meta dictionary holding the format of the elements
+const FORMAT_ITEM: &str = "format";
+//The elements are SecretEntry. A list without format item stores "[ID];[VALUE]" strings and must be migrated.
+const FORMAT_VERSION: u8 = 2;
+//Item of the meta dictionary incremented by each call that modifies the list
+const VERSION_ITEM: &str = "version";
+//Namespace of the index of the trashed IDs in the meta dictionary
+const TRASH_NAMESPACE: &str = "trash:";
+//Prefix of the items of the meta dictionary holding the trashed elements
+const TRASHED_PREFIX: &str = "trashed:";
+//Length of the longest prefix added to an ID in the keys of the meta dictionary
+pub const ID_PREFIX_MAX_LENGTH: usize = {
+    let ids = Index::id_prefix_length(IDS_NAMESPACE);
+    let trash = Index::id_prefix_length(TRASH_NAMESPACE);
+    let longest = if ids > trash { ids } else { trash };
+    if longest > TRASHED_PREFIX.len() {
+        longest
+    } else {
+        TRASHED_PREFIX.len()
+    }
+};
+//Item of the meta dictionary holding the time in milliseconds before a trashed element can be purged
+const RETENTION_ITEM: &str = "retention";
+//Namespace of the history of the changes in the meta dictionary
+const HISTORY_NAMESPACE: &str = "history:";
+//Item of the history holding the number of records
+const HISTORY_LENGTH_ITEM: &str = "length";
+
+/**
+ * pub enum WriteMode - How an element is saved when its ID exists or doesnt exist in the list
+ */
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum WriteMode {
+    //Add the new IDs and replace the existing IDs ("add")
+    Upsert,
+    //Only add new IDs, revert with Error::DuplicateId if an ID exists ("insert")
+    Insert,
+    //Only replace existing IDs, revert with Error::MissingEntry if an ID doesnt exist ("update")
+    Update,
+}
+
+/**
+ * pub struct List - A list is made of two dictionaries :
+ *
+ * [named-key] => the elements, the key of each item is the ID of the element and its value is Some(SecretEntry).
+ *                The value of a removed element is None.
+ * [named-key]_meta => the index of the IDs (see Index), so the elements can be enumerated,
+ *                     the settings of the list, and the trash : the index of the trashed IDs and
+ *                     the items trashed:[ID] => Some(TrashedEntry), None once the element is restored or purged.
+ *                     The history of the changes is appended to the items history:[position] => ChangeRecord,
+ *                     history:length holds the number of records.
+ * Each change of an element is also written as an event (see events).
+ */
+pub struct List {
+    pub name: String,
+    pub entries: URef,
+    pub meta: URef,
+}
+
+impl List {
+    /**
+     * pub fn open - Get the dictionaries of a list
+     *
+     * @param  {type} named_key: &str Name of the list
+     * @return {type}                 The list, or revert if it doesnt exist or must be migrated
+     */
+    pub fn open(named_key: &str) -> List {
+        let list = List::open_any_format(named_key);
+        //The elements of a list created by a previous version of the contract are strings
+        if list.format() < FORMAT_VERSION {
+            runtime::revert(Error::LegacyList);
+        }
+        list
+    }
+
+    /**
+     * fn open_any_format - Get the dictionaries of a list without checking the format of its elements
+     *
+     * @param  {type} named_key: &str Name of the list
+     * @return {type}                 The list, or revert if it doesnt exist or must be migrated
+     */
+    fn open_any_format(named_key: &str) -> List {
+        let meta: URef = match runtime::get_key(&meta_key_name(named_key)) {
+            Some(key) => key.try_into().unwrap_or_revert(),
+            //A list without meta dictionary was created by a previous version of the contract
+            None if runtime::has_key(named_key) => runtime::revert(Error::LegacyList),
+            None => runtime::revert(Error::MissingList),
+        };
+        let entries: URef = runtime::get_key(named_key)
+            .unwrap_or_revert_with(Error::MissingList)
+            .try_into()
+            .unwrap_or_revert();
+        List {
+            name: String::from(named_key),
+            entries,
+            meta,
+        }
+    }
+
+    /**
+     * pub fn index - Get the index of the IDs of the list
+     *
+     * @return {type}  The index
+     */
+    pub fn index(&self) -> Index {
+        Index::new(self.meta, IDS_NAMESPACE)
+    }
+
+    /**
+     * pub fn trash_index - Get the index of the trashed IDs
+     *
+     * @return {type}  The index
+     */
+    pub fn trash_index(&self) -> Index {
+        Index::new(self.meta, TRASH_NAMESPACE)
+    }
+
+    /**
+     * fn format - Get the format of the elements
+     *
+     * @return {type}  FORMAT_VERSION, or 1 for the "[ID];[VALUE]" strings
+     */
+    fn format(&self) -> u8 {
+        storage::dictionary_get(self.meta, FORMAT_ITEM)
+            .unwrap_or_revert()
+            .unwrap_or(1)
+    }
+
+    /**
+     * pub fn retention - Get the time before a trashed element can be purged
+     *
+     * @return {type}  The retention period in milliseconds, 0 if it was never configured
+     */
+    pub fn retention(&self) -> u64 {
+        storage::dictionary_get(self.meta, RETENTION_ITEM)
+            .unwrap_or_revert()
+            .unwrap_or_default()
+    }
+
+    /**
+     * pub fn set_retention - Change the time before a trashed element can be purged
+     *
+     * @param  {type} retention: u64 Retention period in milliseconds
+     * @return {type}                No return value
+     */
+    pub fn set_retention(&self, retention: u64) {
+        storage::dictionary_put(self.meta, RETENTION_ITEM, retention);
+    }
+
+    /**
+     * pub fn version - Get the version of the list
+     *
+     * @return {type}  The number of calls that modified the list, 0 for a new list
+     */
+    pub fn version(&self) -> u64 {
+        storage::dictionary_get(self.meta, VERSION_ITEM)
+            .unwrap_or_revert()
+            .unwrap_or_default()
+    }
+
+    /**
+     * pub fn increment_version - Increment the version of the list, once per call that modifies it
+     *
+     * @return {type}  No return value
+     */
+    pub fn increment_version(&self) {
+        storage::dictionary_put(self.meta, VERSION_ITEM, self.version() + 1);
+    }
+
+    /**
+     * pub fn is_strict - Check if the list only accepts encrypted elements
+     *
+     * @return {type}  true if the VALUE of each new element must be an envelope
+     */
+    pub fn is_strict(&self) -> bool {
+        storage::dictionary_get(self.meta, STRICT_ITEM)
+            .unwrap_or_revert()
+            .unwrap_or_default()
+    }
+
+    /**
+     * pub fn set_strict - Choose if the list only accepts encrypted elements
+     *
+     * @param  {type} strict: bool true to refuse the plaintext elements
+     * @return {type}              No return value
+     */
+    pub fn set_strict(&self, strict: bool) {
+        storage::dictionary_put(self.meta, STRICT_ITEM, strict);
+    }
+
+    /**
+     * pub fn read - Get an element of the list.
+     * Only the IDs of the index are read, the items of the removed IDs can still hold an empty string
+     * saved by a previous version of the contract.
+     *
+     * @param  {type} id: &str ID of the element
+     * @return {type}          The element if it exists
+     */
+    pub fn read(&self, id: &str) -> Option<SecretEntry> {
+        if !self.index().contains(id) {
+            return None;
+        }
+        storage::dictionary_get::<Option<SecretEntry>>(self.entries, id)
+            .unwrap_or_revert()
+            .flatten()
+    }
+
+    /**
+     * fn put - Save an element without recording the change
+     *
+     * @param  {type} entry: SecretEntry Element to save
+     * @return {type}                    true if the ID is new in the list
+     */
+    fn put(&self, entry: SecretEntry) -> bool {
+        let id = entry.id.clone();
+        storage::dictionary_put(self.entries, &id, Some(entry));
+        self.index().push(&id)
+    }
+
+    /**
+     * pub fn write - Add an element, or replace the element having the same ID
+     *
+     * @param  {type} entry: SecretEntry Element to save
+     * @return {type}                    No return value
+     */
+    pub fn write(&self, entry: SecretEntry) {
+        let id = entry.id.clone();
+        let kind = if self.put(entry.clone()) {
+            ChangeKind::Added
+        } else {
+            ChangeKind::Updated
+        };
+        self.record(kind, &id, Some(entry));
+    }
+
+    /**
+     * pub fn remove - Remove an element if it exists
+     *
+     * @param  {type} id: &str ID of the element
+     * @return {type}          true if the element was removed
+     */
+    pub fn remove(&self, id: &str) -> bool {
+        if !self.index().remove(id) {
+            return false;
+        }
+        storage::dictionary_put(self.entries, id, None::<SecretEntry>);
+        self.record(ChangeKind::Deleted, id, None);
+        true
+    }
+
+    /**
+     * pub fn rename - Replace an element by the same element with a new ID, at the same position
+     *
+     * @param  {type} id: &str           ID of the element
+     * @param  {type} entry: SecretEntry Element with its new ID, which must not exist in the list
+     * @return {type}                    No return value
+     */
+    pub fn rename(&self, id: &str, entry: SecretEntry) {
+        let new_id = entry.id.clone();
+        self.index().rename(id, &new_id);
+        storage::dictionary_put(self.entries, id, None::<SecretEntry>);
+        self.record(ChangeKind::Deleted, id, None);
+        storage::dictionary_put(self.entries, &new_id, Some(entry.clone()));
+        self.record(ChangeKind::Added, &new_id, Some(entry));
+    }
+
+    /**
+     * fn record - Append a change of an element to the history of the list and emit its event.
+     * The change belongs to the version of the list after the current call.
+     * Only the digest of the element is recorded, its secret must not outlive a purge.
+     *
+     * @param  {type} kind: ChangeKind            Change of the element
+     * @param  {type} id: &str                    ID of the element
+     * @param  {type} entry: Option<SecretEntry>  The element after the change, None if it left the list
+     * @return {type}                             No return value
+     */
+    fn record(&self, kind: ChangeKind, id: &str, entry: Option<SecretEntry>) {
+        events::emit(
+            &self.name,
+            self.version() + 1,
+            ListEvent::Element { kind, id },
+        );
+        self.append_record(kind, id, entry);
+    }
+
+    /**
+     * fn append_record - Append a change to the history of the list without emitting its event
+     *
+     * @param  {type} kind: ChangeKind            Change of the element, or of the list
+     * @param  {type} id: &str                    ID of the element, or previous name of a renamed list
+     * @param  {type} entry: Option<SecretEntry>  The element after the change, None if it left the list
+     * @return {type}                             No return value
+     */
+    fn append_record(&self, kind: ChangeKind, id: &str, entry: Option<SecretEntry>) {
+        let length_key = format!("{}{}", HISTORY_NAMESPACE, HISTORY_LENGTH_ITEM);
+        let length: u64 = storage::dictionary_get(self.meta, &length_key)
+            .unwrap_or_revert()
+            .unwrap_or_default();
+        let record = ChangeRecord {
+            version: self.version() + 1,
+            kind,
+            id: String::from(id),
+            block_time: u64::from(runtime::get_blocktime()),
+            caller: runtime::get_caller(),
+            digest: entry.map(|entry| runtime::blake2b(entry.to_bytes().unwrap_or_revert())),
+        };
+        storage::dictionary_put(
+            self.meta,
+            &format!("{}{}", HISTORY_NAMESPACE, length),
+            record,
+        );
+        storage::dictionary_put(self.meta, &length_key, length + 1);
+    }
+
+    /**
+     * pub fn read_trashed - Get an element of the trash
+     *
+     * @param  {type} id: &str ID of the element
+     * @return {type}          The trashed element if it exists
+     */
+    pub fn read_trashed(&self, id: &str) -> Option<TrashedEntry> {
+        if !self.trash_index().contains(id) {
+            return None;
+        }
+        storage::dictionary_get::<Option<TrashedEntry>>(self.meta, &trashed_key_name(id))
+            .unwrap_or_revert()
+            .flatten()
+    }
+
+    /**
+     * fn put_in_trash - Save an element in the trash, replacing an element trashed earlier with the same ID
+     *
+     * @param  {type} entry: SecretEntry Element removed from the list
+     * @param  {type} now: u64           Block time of the removal
+     * @return {type}                    No return value
+     */
+    fn put_in_trash(&self, entry: SecretEntry, now: u64) {
+        let id = entry.id.clone();
+        let trashed_entry = TrashedEntry {
+            entry,
+            deleted_at: now,
+        };
+        storage::dictionary_put(self.meta, &trashed_key_name(&id), Some(trashed_entry));
+        self.trash_index().push(&id);
+    }
+
+    /**
+     * pub fn trash - Move an element to the trash if it exists
+     *
+     * @param  {type} id: &str ID of the element
+     * @param  {type} now: u64 Block time of the removal
+     * @return {type}          true if the element was moved
+     */
+    pub fn trash(&self, id: &str, now: u64) -> bool {
+        match self.read(id) {
+            Some(entry) => {
+                self.remove(id);
+                self.put_in_trash(entry, now);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /**
+     * fn take_trashed - Remove an element from the trash without recording the change
+     *
+     * @param  {type} id: &str ID of the element
+     * @return {type}          true if the element was in the trash
+     */
+    fn take_trashed(&self, id: &str) -> bool {
+        if !self.trash_index().remove(id) {
+            return false;
+        }
+        storage::dictionary_put(self.meta, &trashed_key_name(id), None::<TrashedEntry>);
+        true
+    }
+
+    /**
+     * pub fn erase_trashed - Erase an element of the trash if it exists
+     *
+     * @param  {type} id: &str ID of the element
+     * @return {type}          true if the element was erased
+     */
+    pub fn erase_trashed(&self, id: &str) -> bool {
+        if !self.take_trashed(id) {
+            return false;
+        }
+        self.record(ChangeKind::Purged, id, None);
+        true
+    }
+
+    /**
+     * pub fn restore - Move an element of the trash back to the list
+     *
+     * @param  {type} id: &str ID of the element
+     * @param  {type} now: u64 Block time of the restoration
+     * @return {type}          true if the element was in the trash
+     */
+    pub fn restore(&self, id: &str, now: u64) -> bool {
+        let mut entry = match self.read_trashed(id) {
+            Some(trashed_entry) => trashed_entry.entry,
+            None => return false,
+        };
+        self.take_trashed(id);
+        entry.updated_at = now;
+        self.put(entry.clone());
+        self.record(ChangeKind::Restored, id, Some(entry));
+        true
+    }
+}
+
+/**
+ * pub fn meta_key_name - Get the name of the named key of the meta dictionary of a list
+ *
+ * @param  {type} named_key: &str Name of the list
+ * @return {type}                 Name of the meta dictionary
+ */
+pub fn meta_key_name(named_key: &str) -> String {
+    format!("{}{}", named_key, META_SUFFIX)
+}
+
+/**
+ * fn trashed_key_name - Get the name of the item of the meta dictionary holding a trashed element
+ *
+ * @param  {type} id: &str ID of the element
+ * @return {type}          Name of the item
+ */
+fn trashed_key_name(id: &str) -> String {
+    format!("{}{}", TRASHED_PREFIX, id)
+}
+
+/**
+ * pub fn legacy_key_name - Get the name of the named key where a migrated list is kept
+ *
+ * @param  {type} named_key: &str Name of the list
+ * @return {type}                 Name of the legacy list
+ */
+pub fn legacy_key_name(named_key: &str) -> String {
+    format!("{}{}", named_key, LEGACY_SUFFIX)
+}
+
+/**
+ * fn check_unique_ids - Revert if the same ID is used twice in the parameters
+ *
+ * @param  {type} ids: &[&str] IDs received as parameter
+ * @return {type}              No return value
+ */
+fn check_unique_ids(ids: &[&str]) {
+    let mut seen_ids: BTreeSet<&str> = BTreeSet::new();
+    for id in ids {
+        if !seen_ids.insert(*id) {
+            runtime::revert(Error::DuplicateId);
+        }
+    }
+}
+
+/**
+ * fn check_list_name - Revert if a name can't be used for a new list
+ *
+ * @param  {type} named_key: &str Name of the new list
+ * @return {type}                 No return value
+ */
+fn check_list_name(named_key: &str) {
+    //The suffixes are used for the other named keys of a list
+    if named_key.ends_with(META_SUFFIX)
+        || named_key.ends_with(LEGACY_SUFFIX)
+        || named_key.ends_with(ACL_SUFFIX)
+        || named_key.ends_with(DEVICES_SUFFIX)
+        || named_key.ends_with(RECOVERY_SUFFIX)
+        || named_key.ends_with(INHERITANCE_SUFFIX)
+        || named_key == REGISTRY_KEY_NAME
+        || named_key == SHARED_KEY_NAME
+    {
+        runtime::revert(Error::ReservedName);
+    }
+}
+
+/**
+ * fn list_key_names - Get the names of all the named keys that can belong to a list
+ *
+ * @param  {type} named_key: &str Name of the list
+ * @return {type}                 The named key of the elements, of the meta dictionary, of the legacy list
+ *                                of the roles, of the guardians and of the beneficiary of a shared list, and of the devices
+ */
+fn list_key_names(named_key: &str) -> [String; 7] {
+    [
+        String::from(named_key),
+        meta_key_name(named_key),
+        legacy_key_name(named_key),
+        acl::acl_key_name(named_key),
+        devices::devices_key_name(named_key),
+        recovery::recovery_key_name(named_key),
+        inheritance::inheritance_key_name(named_key),
+    ]
+}
+
+/**
+ * pub fn create_lists_if_not_exists - Create an empty list if the named key doesnt exist
+ *
+ * @return {type}  description
+ */
+pub fn create_lists_if_not_exists(named_key: &str) {
+    //Look for the named key
+    match runtime::get_key(named_key) {
+        Some(_key) => {}
+        None => {
+            check_list_name(named_key);
+            //If the named key doesnt exist, we create the dictionaries of an empty list
+            storage::new_dictionary(named_key).unwrap_or_revert();
+            let meta = storage::new_dictionary(&meta_key_name(named_key)).unwrap_or_revert();
+            storage::dictionary_put(meta, FORMAT_ITEM, FORMAT_VERSION);
+            acl::create_if_shared(named_key);
+            registry::register(named_key);
+        }
+    }
+}
+
+/**
+ * pub fn remove_all_elements - Reset the list so that no element are in sync.
+ * Without force, the elements are moved to the trash and can be restored.
+ * With force, the existing dictionaries (or the existing URef of a list created by a previous version of the contract)
+ * are emptied, no new URef is created.
+ *
+ * @param  {type} force: bool If true, the elements are erased instead of moved to the trash
+ * @param  {type} purge: bool If true, the elements are erased, the trash is
+ *                            emptied and the URef kept by the migration of the list is emptied and removed from the named keys
+ * @return {type}             No return value
+ */
+pub fn remove_all_elements(named_key: &str, force: bool, purge: bool) {
+    if !force && !purge {
+        trash_all_elements(named_key);
+        return;
+    }
+
+    //A list created by a previous version of the contract is emptied in place
+    //It has no history and no version, the event is written with the version 0 checked by check_version
+    if !runtime::has_key(&meta_key_name(named_key)) {
+        let key: URef = runtime::get_key(named_key)
+            .unwrap_or_revert_with(Error::MissingList)
+            .try_into()
+            .unwrap_or_revert();
+        let empty_list: Vec<String> = Vec::new();
+        storage::write(key, empty_list);
+        events::emit(named_key, 0, ListEvent::Cleared);
+        return;
+    }
+
+    //The elements are only read through the index, so a list that must be migrated can also be emptied
+    let list = List::open_any_format(named_key);
+    //Empty the index, then empty each element that was in the index
+    for id in list.index().clear() {
+        storage::dictionary_put(list.entries, &id, None::<SecretEntry>);
+        list.record(ChangeKind::Deleted, &id, None);
+    }
+    events::emit(named_key, list.version() + 1, ListEvent::Cleared);
+
+    //The trash and the URef kept by the migration still hold removed elements
+    if purge {
+        for id in list.trash_index().clear() {
+            storage::dictionary_put(list.meta, &trashed_key_name(&id), None::<TrashedEntry>);
+            list.record(ChangeKind::Purged, &id, None);
+        }
+        if let Some(legacy_key) = runtime::get_key(&legacy_key_name(named_key)) {
+            let key: URef = legacy_key.try_into().unwrap_or_revert();
+            let empty_list: Vec<String> = Vec::new();
+            storage::write(key, empty_list);
+            runtime::remove_key(&legacy_key_name(named_key));
+        }
+    }
+    list.increment_version();
+}
+
+/**
+ * fn trash_all_elements - Move all the elements of the list to the trash
+ *
+ * @return {type}  No return value
+ */
+fn trash_all_elements(named_key: &str) {
+    let list = List::open(named_key);
+    let now = u64::from(runtime::get_blocktime());
+    //Empty the index, then move each element that was in the index
+    for id in list.index().clear() {
+        let entry = storage::dictionary_get::<Option<SecretEntry>>(list.entries, &id)
+            .unwrap_or_revert()
+            .flatten()
+            .unwrap_or_revert();
+        storage::dictionary_put(list.entries, &id, None::<SecretEntry>);
+        list.record(ChangeKind::Deleted, &id, None);
+        list.put_in_trash(entry, now);
+    }
+    events::emit(named_key, list.version() + 1, ListEvent::Cleared);
+    list.increment_version();
+}
+
+/**
+ * pub fn create_or_update_secret_code_if_exists - Add or Update a list of string from the existing elements.
+ *
+ * @param  {type} values_to_add: Vec<String>        List of string where the content of each line should be "[ID];[VALUE]"
+ * @param  {type} entries_to_add: Vec<SecretEntry>  Elements in the structured form
+ * @param  {type} mode: WriteMode                   Whether the IDs must already exist, must not exist, or both are accepted
+ * @return {type}                                   No return value
+ */
+pub fn create_or_update_secret_code_if_exists(
+    named_key: &str,
+    values_to_add: Vec<String>,
+    entries_to_add: Vec<SecretEntry>,
+    mode: WriteMode,
+) {
+    let list = List::open(named_key);
+
+    //Check every secret code before modifying the list
+    let mut new_entries: Vec<SecretEntry> = values_to_add
+        .iter()
+        .map(|value_to_add| {
+            SecretCode::parse(value_to_add)
+                .unwrap_or_revert()
+                .into_entry()
+        })
+        .collect();
+    for entry in entries_to_add {
+        SecretCode::check_entry(&entry).unwrap_or_revert();
+        new_entries.push(entry);
+    }
+    let ids: Vec<&str> = new_entries.iter().map(|entry| entry.id.as_str()).collect();
+    check_unique_ids(&ids);
+    //A strict list only accepts encrypted elements
+    if list.is_strict() {
+        for entry in new_entries.iter() {
+            SecretCode::check_envelope(&entry.secret).unwrap_or_revert();
+        }
+    }
+
+    //Loop through each secret code that we want to add
+    //The element is saved under its ID, so an existing element with the same ID is updated
+    let now = u64::from(runtime::get_blocktime());
+    for mut entry in new_entries {
+        //The timestamps and the pin are set by the contract, an updated element keeps its creation date and its pin
+        let existing_entry = list.read(&entry.id);
+        entry.created_at = match (&existing_entry, mode) {
+            (Some(_), WriteMode::Insert) => runtime::revert(Error::DuplicateId),
+            (Some(existing_entry), _) => existing_entry.created_at,
+            (None, WriteMode::Update) => runtime::revert(Error::MissingEntry),
+            (None, _) => now,
+        };
+        entry.updated_at = now;
+        entry.pinned = existing_entry.map_or(false, |existing_entry| existing_entry.pinned);
+        list.write(entry);
+    }
+    list.increment_version();
+}
+
+/**
+ * pub fn remove_secret_code_if_exists - Move a list of string from the existing elements to the trash. If an element doesnt exist, we ignore the element.
+ * With a tag, an element that doesnt carry the tag is also ignored.
+ *
+ * @param  {type} values_to_remove: Vec<String> List of string where the content of each line should be "[ID];[VALUE]"
+ * @param  {type} tag: Option<String>           Only remove the elements carrying the tag or in the folder of the same name
+ * @return {type}                               No return value
+ */
+pub fn remove_secret_code_if_exists(
+    named_key: &str,
+    values_to_remove: Vec<String>,
+    tag: Option<String>,
+) {
+    let list = List::open(named_key);
+
+    //Check every secret code before modifying the list
+    let ids: Vec<&str> = values_to_remove
+        .iter()
+        .map(|value_to_remove| SecretCode::parse_id(value_to_remove).unwrap_or_revert())
+        .collect();
+    check_unique_ids(&ids);
+    if let Some(tag) = tag.as_ref() {
+        SecretCode::check_tag(tag).unwrap_or_revert();
+    }
+
+    //Loop through each secret code that we want to remove
+    let now = u64::from(runtime::get_blocktime());
+    for id in ids {
+        if let Some(tag) = tag.as_ref() {
+            if !list.read(id).map_or(false, |entry| entry.matches_tag(tag)) {
+                continue;
+            }
+        }
+        list.trash(id, now);
+    }
+    list.increment_version();
+}
+
+/**
+ * pub fn remove_tagged_elements - Move all the elements carrying a tag, or in the folder of the same name, to the trash
+ *
+ * @param  {type} tag: String Tag or folder path of the elements
+ * @return {type}             No return value
+ */
+pub fn remove_tagged_elements(named_key: &str, tag: String) {
+    let ids = List::open(named_key).index().ids();
+    remove_secret_code_if_exists(named_key, ids, Some(tag));
+}
+
+/**
+ * pub fn restore_secret_codes - Move elements from the trash back to the list
+ *
+ * @param  {type} values_to_restore: Vec<String> List of string where the content of each line should be "[ID]" or "[ID];[VALUE]"
+ * @return {type}                                No return value
+ */
+pub fn restore_secret_codes(named_key: &str, values_to_restore: Vec<String>) {
+    let list = List::open(named_key);
+
+    //Check every secret code before modifying the list
+    let ids: Vec<&str> = values_to_restore
+        .iter()
+        .map(|value_to_restore| SecretCode::parse_id(value_to_restore).unwrap_or_revert())
+        .collect();
+    check_unique_ids(&ids);
+
+    let now = u64::from(runtime::get_blocktime());
+    let strict = list.is_strict();
+    for id in ids {
+        //An element added with the same ID since the removal is not replaced
+        if list.read(id).is_some() {
+            runtime::revert(Error::DuplicateId);
+        }
+        //A strict list doesnt take back a plaintext element trashed before the list became strict
+        if let Some(trashed_entry) = list.read_trashed(id).filter(|_| strict) {
+            SecretCode::check_envelope(&trashed_entry.entry.secret).unwrap_or_revert();
+        }
+        if !list.restore(id, now) {
+            runtime::revert(Error::MissingEntry);
+        }
+    }
+    list.increment_version();
+}
+
+/**
+ * pub fn purge_trash - Erase the trashed elements whose retention period is over
+ *
+ * @param  {type} values_to_purge: Option<Vec<String>> IDs to erase, "[ID]" or "[ID];[VALUE]". Revert if one of them
+ *                                                     is not in the trash or is still retained.
+ *                                                     If None, every element whose retention period is over is erased.
+ * @return {type}                                      No return value
+ */
+pub fn purge_trash(named_key: &str, values_to_purge: Option<Vec<String>>) {
+    let list = List::open(named_key);
+    let now = u64::from(runtime::get_blocktime());
+    let retention = list.retention();
+    let is_retained =
+        |trashed_entry: &TrashedEntry| now < trashed_entry.deleted_at.saturating_add(retention);
+
+    match values_to_purge {
+        Some(values_to_purge) => {
+            let ids: Vec<&str> = values_to_purge
+                .iter()
+                .map(|value_to_purge| SecretCode::parse_id(value_to_purge).unwrap_or_revert())
+                .collect();
+            check_unique_ids(&ids);
+            for id in ids {
+                let trashed_entry = list
+                    .read_trashed(id)
+                    .unwrap_or_revert_with(Error::MissingEntry);
+                if is_retained(&trashed_entry) {
+                    runtime::revert(Error::RetentionNotElapsed);
+                }
+                list.erase_trashed(id);
+            }
+        }
+        None => {
+            for id in list.trash_index().ids() {
+                let trashed_entry = list.read_trashed(&id).unwrap_or_revert();
+                if !is_retained(&trashed_entry) {
+                    list.erase_trashed(&id);
+                }
+            }
+        }
+    }
+    list.increment_version();
+}
+
+/**
+ * pub fn migrate_secret_codes - Convert a list created by a previous version of the contract, where all
+ * the elements were saved in a single URef, to the dictionaries of a list.
+ * The previous versions could also save the same ID several times (they matched the IDs with a substring
+ * search), only the most recent element (the last one) of each ID is kept. Elements that were already
+ * removed by mistake can't be recovered.
+ * The URef of the previous list is kept under the named key [named-key]_legacy.
+ * A list whose dictionaries hold "[ID];[VALUE]" strings is converted in place to SecretEntry, revert with
+ * Error::PlaintextEntry if the list is strict and a VALUE is not encrypted.
+ *
+ * @return {type}  description
+ */
+pub fn migrate_secret_codes(named_key: &str) {
+    let now = u64::from(runtime::get_blocktime());
+    //A list which already has its dictionaries only needs the conversion of its elements
+    if runtime::has_key(&meta_key_name(named_key)) {
+        let list = List::open_any_format(named_key);
+        //A list created before the registry is registered
+        registry::register(named_key);
+        if list.format() < FORMAT_VERSION {
+            let strict = list.is_strict();
+            for id in list.index().ids() {
+                let secret_code: String = storage::dictionary_get(list.entries, &id)
+                    .unwrap_or_revert()
+                    .unwrap_or_revert();
+                let entry = migrated_entry(SecretCode::split(&secret_code), now);
+                //The elements saved before the list became strict can still be plaintext
+                if strict {
+                    SecretCode::check_envelope(&entry.secret).unwrap_or_revert();
+                }
+                storage::dictionary_put(list.entries, &id, Some(entry));
+            }
+            storage::dictionary_put(list.meta, FORMAT_ITEM, FORMAT_VERSION);
+            events::emit(named_key, list.version() + 1, ListEvent::Migrated);
+            list.increment_version();
+        }
+        return;
+    }
+    //Look for the named key
+    match runtime::get_key(named_key) {
+        Some(_key) => {
+            //Get the URref of the named key
+            let key: URef = _key.try_into().unwrap_or_revert();
+            //Get the value of the URef
+            let existing_secret_codes: Vec<String> =
+                storage::read(key).unwrap_or_revert().unwrap_or_revert();
+
+            //Keep the previous list under another named key, then create the dictionaries
+            runtime::remove_key(named_key);
+            runtime::put_key(&legacy_key_name(named_key), key.into());
+            create_lists_if_not_exists(named_key);
+            let list = List::open(named_key);
+
+            //The elements are saved from the oldest to the most recent, so the latest value of a duplicated ID wins
+            //An element whose ID can't be used as a dictionary key is only kept in the legacy list
+            for secret_code in existing_secret_codes.iter() {
+                let secret_code = SecretCode::split(secret_code);
+                if SecretCode::check_id(secret_code.id).is_ok() {
+                    list.write(migrated_entry(secret_code, now));
+                }
+            }
+            events::emit(named_key, list.version() + 1, ListEvent::Migrated);
+            list.increment_version();
+        }
+        None => runtime::revert(Error::MissingList),
+    }
+}
+
+/**
+ * fn migrated_entry - Convert an element saved by a previous version of the contract
+ *
+ * @param  {type} secret_code: SecretCode The ID and the VALUE of the element
+ * @param  {type} now: u64                Block time of the migration
+ * @return {type}                         The element in the structured form
+ */
+fn migrated_entry(secret_code: SecretCode, now: u64) -> SecretEntry {
+    let mut entry = secret_code.into_entry();
+    entry.created_at = now;
+    entry.updated_at = now;
+    entry
+}
+
+/**
+ * fn update_counter - Change the counter of a counter-based element
+ *
+ * @param  {type} id: &str                              ID of the element
+ * @param  {type} next_counter: impl FnOnce(u64) -> u64 New counter from the stored counter
+ * @return {type}                                       The new counter
+ */
+fn update_counter(named_key: &str, id: &str, next_counter: impl FnOnce(u64) -> u64) -> u64 {
+    let list = List::open(named_key);
+    SecretCode::check_id(id).unwrap_or_revert();
+    let mut entry = list.read(id).unwrap_or_revert_with(Error::MissingEntry);
+    if entry.kind != OtpKind::Hotp {
+        runtime::revert(Error::NotCounterBased);
+    }
+
+    let counter = next_counter(entry.counter);
+    if counter != entry.counter {
+        entry.counter = counter;
+        entry.updated_at = u64::from(runtime::get_blocktime());
+        list.write(entry);
+        list.increment_version();
+    }
+    counter
+}
+
+/**
+ * pub fn increment_counter - Move the counter of a counter-based element to the next password
+ *
+ * @param  {type} id: &str ID of the element
+ * @return {type}          The new counter
+ */
+pub fn increment_counter(named_key: &str, id: &str) -> u64 {
+    update_counter(named_key, id, |counter| counter.saturating_add(1))
+}
+
+/**
+ * pub fn resync_counter - Move the counter of a counter-based element to the counter observed on a device.
+ * The counter only moves forward, an observed counter lower than the stored counter is ignored.
+ *
+ * @param  {type} id: &str                 ID of the element
+ * @param  {type} observed_counter: u64    Counter of the device
+ * @return {type}                          The new counter
+ */
+pub fn resync_counter(named_key: &str, id: &str, observed_counter: u64) -> u64 {
+    update_counter(named_key, id, |counter| counter.max(observed_counter))
+}
+
+/**
+ * pub fn reorder_elements - Put the elements of the list in a new order
+ *
+ * @param  {type} values_to_reorder: Vec<String> IDs of all the elements of the list, in their new order
+ * @return {type}                                No return value
+ */
+pub fn reorder_elements(named_key: &str, values_to_reorder: Vec<String>) {
+    let list = List::open(named_key);
+    let index = list.index();
+
+    //Check that the IDs are a permutation of the index before modifying the list
+    let ids: Vec<&str> = values_to_reorder
+        .iter()
+        .map(|value_to_reorder| SecretCode::parse_id(value_to_reorder).unwrap_or_revert())
+        .collect();
+    check_unique_ids(&ids);
+    if ids.len() as u64 != index.len() || !ids.iter().all(|id| index.contains(id)) {
+        runtime::revert(Error::InvalidOrder);
+    }
+
+    index.reorder(&ids);
+    events::emit(named_key, list.version() + 1, ListEvent::Reordered);
+    list.increment_version();
+}
+
+/**
+ * pub fn pin_element - Pin an element, so it is returned before the others by "get", or unpin it
+ *
+ * @param  {type} id: &str      ID of the element
+ * @param  {type} pinned: bool  false to unpin the element
+ * @return {type}               No return value
+ */
+pub fn pin_element(named_key: &str, id: &str, pinned: bool) {
+    let list = List::open(named_key);
+    SecretCode::check_id(id).unwrap_or_revert();
+    let mut entry = list.read(id).unwrap_or_revert_with(Error::MissingEntry);
+    if entry.pinned != pinned {
+        entry.pinned = pinned;
+        entry.updated_at = u64::from(runtime::get_blocktime());
+        list.write(entry);
+        list.increment_version();
+    }
+}
+
+/**
+ * fn check_new_entry - Check an element before it is saved in a list
+ *
+ * @param  {type} list: &List         List where the element is saved
+ * @param  {type} entry: &SecretEntry Element to check
+ * @return {type}                     No return value
+ */
+fn check_new_entry(list: &List, entry: &SecretEntry) {
+    SecretCode::check_entry(entry).unwrap_or_revert();
+    //A strict list only accepts encrypted elements
+    if list.is_strict() {
+        SecretCode::check_envelope(&entry.secret).unwrap_or_revert();
+    }
+}
+
+/**
+ * fn apply_operation - Apply an operation of a batch
+ *
+ * @param  {type} list: &List                       List of the batch
+ * @param  {type} operation: Operation              Operation to apply
+ * @param  {type} now: u64                          Block time saved in the modified elements
+ * @param  {type} moved_to: &mut BTreeSet<String>   Names of the other lists receiving the moved elements
+ * @return {type}                                   No return value
+ */
+fn apply_operation(list: &List, operation: Operation, now: u64, moved_to: &mut BTreeSet<String>) {
+    SecretCode::check_id(operation.id()).unwrap_or_revert();
+    //An add-only device can send a batch of new elements, the other operations change or remove existing elements
+    if !matches!(operation, Operation::Add(_)) {
+        devices::check(&list.name, DeviceScope::Full);
+    }
+    match operation {
+        Operation::Add(mut entry) => {
+            check_new_entry(list, &entry);
+            if list.read(&entry.id).is_some() {
+                runtime::revert(Error::DuplicateId);
+            }
+            entry.created_at = now;
+            entry.updated_at = now;
+            entry.pinned = false;
+            list.write(entry);
+        }
+        Operation::Upsert(mut entry) => {
+            check_new_entry(list, &entry);
+            let existing_entry = list.read(&entry.id);
+            entry.created_at = existing_entry
+                .as_ref()
+                .map_or(now, |existing_entry| existing_entry.created_at);
+            entry.updated_at = now;
+            entry.pinned = existing_entry.map_or(false, |existing_entry| existing_entry.pinned);
+            list.write(entry);
+        }
+        Operation::Delete(id) => {
+            if !list.trash(&id, now) {
+                runtime::revert(Error::MissingEntry);
+            }
+        }
+        Operation::RenameId { id, new_id, secret } => {
+            SecretCode::check_id(&new_id).unwrap_or_revert();
+            let mut entry = list.read(&id).unwrap_or_revert_with(Error::MissingEntry);
+            //The ID is the associated data of an envelope, the old envelope could never be opened under the new ID
+            match secret {
+                Some(secret) => {
+                    SecretCode::check_envelope(&secret).unwrap_or_revert();
+                    entry.secret = secret;
+                }
+                None if SecretCode::is_sealed(&entry.secret) => runtime::revert(Error::SealedEntry),
+                None => {}
+            }
+            entry.id = new_id;
+            check_new_entry(list, &entry);
+            if list.read(&entry.id).is_some() {
+                runtime::revert(Error::DuplicateId);
+            }
+            entry.updated_at = now;
+            list.rename(&id, entry);
+        }
+        Operation::Move { id, named_key } => {
+            let mut entry = list.read(&id).unwrap_or_revert_with(Error::MissingEntry);
+            acl::check(&named_key, Role::Writer);
+            //The other list only gets a new element
+            devices::check(&named_key, DeviceScope::AddOnly);
+            create_lists_if_not_exists(&named_key);
+            let other_list = List::open(&named_key);
+            check_new_entry(&other_list, &entry);
+            //Moving an element to its own list is refused the same way
+            if other_list.read(&id).is_some() {
+                runtime::revert(Error::DuplicateId);
+            }
+            entry.updated_at = now;
+            list.remove(&id);
+            other_list.write(entry);
+            //The version of the other list is incremented once, at the end of the batch
+            moved_to.insert(named_key);
+        }
+    }
+}
+
+/**
+ * pub fn apply_operations - Apply the operations of a batch in their order. An invalid operation
+ * reverts the deploy, so none of the operations is saved.
+ * The list and each list receiving moved elements get a single new version.
+ *
+ * @param  {type} operations: Vec<Operation> Operations to apply
+ * @return {type}                            No return value
+ */
+pub fn apply_operations(named_key: &str, operations: Vec<Operation>) {
+    let list = List::open(named_key);
+    let now = u64::from(runtime::get_blocktime());
+    let mut moved_to: BTreeSet<String> = BTreeSet::new();
+    for operation in operations {
+        apply_operation(&list, operation, now, &mut moved_to);
+    }
+    list.increment_version();
+    for other_named_key in moved_to.iter() {
+        List::open(other_named_key).increment_version();
+    }
+}
+
+/**
+ * pub fn rename_list - Move the named keys of a list to a new name. The elements are not modified,
+ * the rename is recorded in the history of the list and increments its version.
+ *
+ * @param  {type} new_named_key: &str New name of the list, it must not be used by a named key
+ * @return {type}                     No return value
+ */
+pub fn rename_list(named_key: &str, new_named_key: &str) {
+    List::open(named_key);
+    check_list_name(new_named_key);
+    let new_key_names = list_key_names(new_named_key);
+    if new_key_names.iter().any(|name| runtime::has_key(name)) {
+        runtime::revert(Error::ListExists);
+    }
+
+    for (key_name, new_key_name) in list_key_names(named_key).iter().zip(new_key_names.iter()) {
+        if let Some(key) = runtime::get_key(key_name) {
+            runtime::remove_key(key_name);
+            runtime::put_key(new_key_name, key);
+        }
+    }
+    registry::rename(named_key, new_named_key);
+
+    let list = List::open(new_named_key);
+    events::emit(
+        new_named_key,
+        list.version() + 1,
+        ListEvent::Renamed {
+            previous_named_key: named_key,
+        },
+    );
+    list.append_record(ChangeKind::Renamed, named_key, None);
+    list.increment_version();
+}
+
+/**
+ * pub fn copy_list - Copy the elements of a list into another list, created if it doesnt exist.
+ * An element of the other list with the same ID is replaced. The copies are dated with the time of the copy
+ * and keep the pin of the element they replace, like the elements saved by "add".
+ *
+ * @param  {type} new_named_key: &str Name of the other list
+ * @return {type}                     No return value
+ */
+pub fn copy_list(named_key: &str, new_named_key: &str) {
+    let entries = read_secret_codes(named_key);
+    acl::check(new_named_key, Role::Writer);
+    //"copy" replaces the elements having the same ID
+    devices::check(new_named_key, DeviceScope::Full);
+    create_lists_if_not_exists(new_named_key);
+    let list = List::open(new_named_key);
+
+    //A strict list only accepts encrypted elements
+    if list.is_strict() {
+        for entry in entries.iter() {
+            SecretCode::check_envelope(&entry.secret).unwrap_or_revert();
+        }
+    }
+    let now = u64::from(runtime::get_blocktime());
+    for mut entry in entries {
+        entry.created_at = now;
+        entry.updated_at = now;
+        entry.pinned = list
+            .read(&entry.id)
+            .map_or(false, |existing_entry| existing_entry.pinned);
+        list.write(entry);
+    }
+    list.increment_version();
+}
+
+/**
+ * pub fn delete_list - Erase the elements of a list, then remove its named keys and its name from the registry
+ *
+ * @return {type}  No return value
+ */
+pub fn delete_list(named_key: &str) {
+    remove_all_elements(named_key, true, true);
+    for key_name in list_key_names(named_key).iter() {
+        runtime::remove_key(key_name);
+    }
+    registry::unregister(named_key);
+}
+
+/**
+ * pub fn configure_list - Change the settings of a list. A setting that is not given is not modified.
+ *
+ * @param  {type} strict: Option<bool> true to refuse the elements whose VALUE is not an envelope.
+ *                                     The existing elements are not checked.
+ * @param  {type} retention: Option<u64> Time in milliseconds before a trashed element can be purged
+ * @return {type}                      No return value
+ */
+pub fn configure_list(named_key: &str, strict: Option<bool>, retention: Option<u64>) {
+    //The settings dont depend on the format of the elements, a list can be made lenient before its migration
+    let list = List::open_any_format(named_key);
+    if let Some(strict) = strict {
+        list.set_strict(strict);
+    }
+    if let Some(retention) = retention {
+        list.set_retention(retention);
+    }
+    events::emit(named_key, list.version() + 1, ListEvent::Configured);
+    list.increment_version();
+}
+
+/**
+ * pub fn check_version - Check that the list was not modified since the caller read it.
+ * A list that doesnt exist yet has the version 0.
+ *
+ * @param  {type} expected_version: Option<u64> Version read by the caller, nothing is checked if None
+ * @return {type}                               No return value, revert with Error::VersionConflict
+ */
+pub fn check_version(named_key: &str, expected_version: Option<u64>) {
+    if let Some(expected_version) = expected_version {
+        let version = if runtime::has_key(&meta_key_name(named_key)) {
+            List::open_any_format(named_key).version()
+        } else {
+            0
+        };
+        if version != expected_version {
+            runtime::revert(Error::VersionConflict);
+        }
+    }
+}
+
+/**
+ * pub fn read_secret_codes - Get the elements of the list
+ *
+ * @return {type}  The pinned elements then the other elements, each in the order of the index
+ */
+pub fn read_secret_codes(named_key: &str) -> Vec<SecretEntry> {
+    let list = List::open(named_key);
+    let (mut entries, others): (Vec<SecretEntry>, Vec<SecretEntry>) = list
+        .index()
+        .ids()
+        .iter()
+        .map(|id| list.read(id).unwrap_or_revert())
+        .partition(|entry| entry.pinned);
+    entries.extend(others);
+    entries
+}
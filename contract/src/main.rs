@@ -7,160 +7,105 @@ compile_error!("target arch should be wasm32: compile with '--target wasm32-unkn
 extern crate alloc;
 
 use alloc::string::String;
-use alloc::vec::Vec;
 
-use casper_contract::{
-    contract_api::{runtime, storage},
-    unwrap_or_revert::UnwrapOrRevert,
-};
-use casper_types::URef;
-use core::convert::TryInto;
-
-const DATA_ARG_NAME: &str = "keys";
-const METHOD_ARG_NAME: &str = "method";
-const NAMED_KEY_ARG_NAME: &str = "named-key";
-
-/**
- * pub fn create_lists_if_not_exists - Create an empty list if the named key doesnt exist
- *
- * @return {type}  description
- */
-pub fn create_lists_if_not_exists(named_key: &str) {
-    //Look for the named key
-    match runtime::get_key(named_key) {
-        Some(_key) => {}
-        None => {
-            //If the named key doesnt exist, we create an empty list and save it under the NAMED_KEY_ARG_NAME
-            let empty_list: Vec<&str> = Vec::new();
-            let key = storage::new_uref(empty_list).into();
-            runtime::put_key(named_key, key);
-        }
-    }
-}
-
-/**
- * pub fn remove_all_elements - Reset the list so that no element are in sync
- *
- * @return {type}  description
- */
-pub fn remove_all_elements(named_key: &str) {
-    //Look for the named key
-    match runtime::get_key(named_key) {
-        Some(_key) => {
-            let empty_list: Vec<&str> = Vec::new();
-            let key = storage::new_uref(empty_list).into();
-            runtime::put_key(named_key, key);
-        }
-        None => {}
-    }
-}
-
-/**
- * pub fn create_or_update_secret_code_if_exists - Add or Update a list of string from the existing elements.
- *
- * @param  {type} values_to_add: Vec<String> List of string where the content of each line should be "[ID];[VALUE]"
- * @return {type}                               No return value
- */
-pub fn create_or_update_secret_code_if_exists(named_key: &str, values_to_add: Vec<String>) {
-    //Look for the named key
-    match runtime::get_key(named_key) {
-        Some(_key) => {
-            //Get the URref of the named key
-            let key: URef = _key.try_into().unwrap_or_revert();
-            //Get the value of the URef
-            let mut existing_secret_codes: Vec<String> =
-                storage::read(key).unwrap_or_revert().unwrap_or_revert();
-
-            //Loop through each secret code that we want to add
-            for value_to_add in values_to_add.into_iter() {
-                //Split the content to get the ID and the VALUE
-                let secret_code_elements: Vec<&str> = value_to_add.as_str().split(";").collect();
-                //Search if we have the ID in the existing secret codes
-                //If yes, we remove it. So we can update it.
-                if let Some(index) = existing_secret_codes
-                    .iter()
-                    .position(|r| r.contains(secret_code_elements[0]))
-                {
-                    existing_secret_codes.remove(index);
-                }
-                existing_secret_codes.push(value_to_add);
-            }
-            //Once we are done, we save our content
-            storage::write(key, existing_secret_codes);
-        }
-        None => {}
-    }
-}
-
-/**
- * pub fn remove_secret_code_if_exists - Remove a list of string from the existing elements. If an element doesnt exist, we ignore the element.
- *
- * @param  {type} values_to_remove: Vec<String> List of string where the content of each line should be "[ID];[VALUE]"
- * @return {type}                               No return value
- */
-pub fn remove_secret_code_if_exists(named_key: &str, values_to_remove: Vec<String>) {
-    //Look for the named key
-    match runtime::get_key(named_key) {
-        Some(_key) => {
-            //Get the URref of the named key
-            let key: URef = _key.try_into().unwrap_or_revert();
-            //Get the value of the URef
-            let mut existing_secret_codes: Vec<String> =
-                storage::read(key).unwrap_or_revert().unwrap_or_revert();
-
-            //If we have secret codes
-            if existing_secret_codes.len() > 0 {
-                //Loop through each secret code that we want to remove
-                for value_to_remove in values_to_remove.into_iter() {
-                    //Split the content to get the ID and the VALUE
-                    let secret_code_elements: Vec<&str> =
-                        value_to_remove.as_str().split(";").collect();
-                    //Search if we have the ID in the existing secret codes
-                    //If yes, we remove it
-                    if let Some(index) = existing_secret_codes
-                        .iter()
-                        .position(|r| r.contains(secret_code_elements[0]))
-                    {
-                        existing_secret_codes.remove(index);
-                    }
-                }
-                //Once we are done, we save our content
-                storage::write(key, existing_secret_codes);
-            }
-        }
-        None => {}
-    }
-}
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use contract::{Error, Method, METHOD_ARG_NAME};
 
 /**
  * Objective : Store a list of String in the blockchain in the named key "blockchain-authenticator"
  *
  * Parameters :
  *
+ * named-key : name of the named key where the list is stored
+ *
  * keys : should be a list of string where the content of each line should be "[ID];[VALUE]"
  *
+ * entries : list of SecretEntry, the structured form of the elements (with "add", "insert" and
+ *           "update", in addition to or instead of "keys")
+ *
+ * uris : list of otpauth URIs "otpauth://totp/[issuer]:[account]?secret=[secret]&...", converted to
+ *        elements whose ID is the label (with "add", "insert" and "update")
+ *
+ * id : ID of the element (with "increment", "resync" and "pin")
+ *
+ * counter : counter observed on the device (with "resync")
+ *
+ * pinned : false to unpin the element (with "pin")
+ *
+ * new-named-key : name of the other list (with "rename" and "copy")
+ *
+ * expected-version : optional, version of the list read by the caller. The call is reverted if the
+ *                    list was modified since
+ *
+ * operations : list of Operation (add, upsert, delete, rename-ID or move to another list), with "batch"
+ *
  * method : add => Add the list to the current elements
- *          del => remove the list to the current elements
+ *          insert => like add, but revert if an ID already exists
+ *          update => like add, but revert if an ID doesnt exist
+ *          del => move the list from the current elements to the trash
+ *          delall => move all the elements to the trash, or erase them with force
+ *          restore => move the list from the trash back to the current elements
+ *          purge => erase the list (or all the elements) from the trash once the retention period is over
+ *          migrate => remove the duplicated IDs left by the previous versions of the contract
+ *          get => return the elements of the list
+ *          configure => change the settings of the list
+ *          increment => move the counter of a counter-based element to the next password
+ *          resync => move the counter of a counter-based element forward to the counter of a device
+ *          lists => return the names of the lists created by the contract (without named-key)
+ *          rename => move the list to new-named-key
+ *          copy => copy the elements of the list into new-named-key
+ *          dellist => remove the list and its named keys
+ *          batch => apply the operations in their order, the deploy is reverted if one of them is invalid
+ *          reorder => put the elements in the order of the keys, which must be the IDs of all the elements
+ *          pin => pin the element id, so get returns it before the others (unpin it if pinned is false)
+ *          grant => give a role to an account on a shared list (only with the stored contract)
+ *          revoke => remove the role of an account on a shared list (only with the stored contract)
+ *          adddevice => allow a key to call the list, the deploys must then be signed by one of the devices
+ *          deldevice => remove a key from the devices of the list
+ *          setguardians => choose the guardians of a shared list (only with the stored contract)
+ *          approverecovery => approve, as a guardian, giving a shared list to new-owner
+ *          cancelrecovery => cancel the pending recovery of a shared list
+ *          completerecovery => give a shared list to the approved account once the timelock is over
+ *          setbeneficiary => choose the account that can claim a shared list when its owner is inactive
+ *          heartbeat => tell the contract that the owner of a shared list is still active
+ *          claim => give the role chosen by the owner to the beneficiary once the owner is inactive
+ *
+ * strict : optional, used by configure. If true, the VALUE of each element added to the list must be
+ *          an encrypted envelope "v1;[nonce];[ciphertext]" (see the envelope crate)
+ *
+ * retention : optional, used by configure. Time in milliseconds before a trashed element can be purged
+ *
+ * force : optional, used by delall. If true, the elements are erased instead of moved to the trash
+ *
+ * tag : optional, used by del and delall. Only the elements carrying the tag, or in the folder of the same name
+ *       (or one of its subfolders), are moved to the trash. The force of delall is then ignored
+ *
+ * account : account hash of grant and revoke
+ *
+ * role : role given by grant, or by the claim of the beneficiary of setbeneficiary, 0 for reader, 1 for writer, 2 for owner
+ *
+ * device : public key of adddevice and deldevice
+ *
+ * label : name of the device of adddevice
+ *
+ * scope : methods allowed to the device of adddevice, 0 for read-only, 1 for add-only, 2 for full
+ *
+ * guardians, threshold, timelock : accounts of setguardians, number of guardians who must approve a recovery,
+ *                                  and time in milliseconds between their approval and the recovery
+ *
+ * new-owner : account of approverecovery
+ *
+ * beneficiary, inactivity : account of setbeneficiary, and time in milliseconds without add, del or heartbeat
+ *                           of the owner before the claim
+ *
+ * Any invalid parameter reverts the deploy with one of the codes of the Error enum
  *
  **/
 #[no_mangle]
 pub extern "C" fn call() {
-    //let data: Vec<String> = runtime::get_named_arg(DATA_ARG_NAME);
     let method: String = runtime::get_named_arg(METHOD_ARG_NAME);
-    let named_key: String = runtime::get_named_arg(NAMED_KEY_ARG_NAME);
 
-    //We create the named key if it doesnt exist
-    create_lists_if_not_exists(named_key.as_str());
-    //If the method is "add", we add the elements in parameter to the existing elements
-    if method == "add" {
-        create_or_update_secret_code_if_exists(
-            named_key.as_str(),
-            runtime::get_named_arg(DATA_ARG_NAME),
-        );
-    } else if method == "del" {
-        //We remove the existing elements
-        remove_secret_code_if_exists(named_key.as_str(), runtime::get_named_arg(DATA_ARG_NAME));
-    } else if method == "delall" {
-        remove_all_elements(named_key.as_str());
-    }
+    contract::execute(
+        Method::from_name(method.as_str()).unwrap_or_revert_with(Error::UnknownMethod),
+    );
 }
@@ -0,0 +1,159 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use blockchain_authenticator_client::{Recovery, RecoveryRequest, Role};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{account::AccountHash, URef};
+use core::convert::TryInto;
+
+use crate::acl;
+use crate::devices;
+use crate::error::Error;
+
+//Suffix of the named key of the URef holding the guardians of a shared list and the pending recovery
+pub const RECOVERY_SUFFIX: &str = "_recovery";
+
+/**
+ * pub fn recovery_key_name - Get the named key of the guardians of a list
+ *
+ * @param  {type} named_key: &str Name of the list
+ * @return {type}                 The named key of the URef [named-key]_recovery
+ */
+pub fn recovery_key_name(named_key: &str) -> String {
+    format!("{}{}", named_key, RECOVERY_SUFFIX)
+}
+
+/**
+ * fn read - Get the guardians of a list
+ *
+ * @param  {type} named_key: &str Name of the list
+ * @return {type}                 The guardians and the pending recovery, None if the owner didnt choose guardians
+ */
+fn read(named_key: &str) -> Option<Recovery> {
+    let key = runtime::get_key(&recovery_key_name(named_key))?;
+    let uref: URef = key.try_into().unwrap_or_revert();
+    storage::read(uref).unwrap_or_revert()
+}
+
+/**
+ * fn write - Save the guardians of a list, the URef is created on the first call
+ *
+ * @param  {type} recovery: Recovery The guardians and the pending recovery
+ * @return {type}                    No return value
+ */
+fn write(named_key: &str, recovery: Recovery) {
+    match runtime::get_key(&recovery_key_name(named_key)) {
+        Some(key) => {
+            let uref: URef = key.try_into().unwrap_or_revert();
+            storage::write(uref, recovery);
+        }
+        None => runtime::put_key(
+            &recovery_key_name(named_key),
+            storage::new_uref(recovery).into(),
+        ),
+    }
+}
+
+/**
+ * pub fn set_guardians - Choose the guardians of a shared list, the caller becomes the owner replaced by a recovery.
+ * A pending recovery is cancelled.
+ *
+ * @param  {type} guardians: Vec<AccountHash> Accounts that can approve a recovery
+ * @param  {type} threshold: u32              Number of guardians who must approve the same new owner
+ * @param  {type} timelock: u64               Time in milliseconds between the approval and the recovery
+ * @return {type}                             No return value
+ */
+pub fn set_guardians(named_key: &str, guardians: Vec<AccountHash>, threshold: u32, timelock: u64) {
+    //A guardian cant give a list of another account, only the contract can hand over a shared list
+    if !acl::is_shared(named_key) {
+        runtime::revert(Error::NotShared);
+    }
+    let distinct_guardians = guardians
+        .iter()
+        .enumerate()
+        .all(|(position, guardian)| !guardians[..position].contains(guardian));
+    if threshold == 0 || threshold as usize > guardians.len() || !distinct_guardians {
+        runtime::revert(Error::InvalidGuardians);
+    }
+    write(
+        named_key,
+        Recovery {
+            owner: runtime::get_caller(),
+            guardians,
+            threshold,
+            timelock,
+            request: None,
+        },
+    );
+}
+
+/**
+ * pub fn approve - Approve, as a guardian, giving the list to a new owner. The timelock starts when
+ * the threshold is reached.
+ *
+ * @param  {type} new_owner: AccountHash Account that becomes the owner of the list
+ * @return {type}                        No return value
+ */
+pub fn approve(named_key: &str, new_owner: AccountHash) {
+    let caller = runtime::get_caller();
+    let mut recovery = read(named_key).unwrap_or_revert_with(Error::NotGuardian);
+    if !recovery.guardians.contains(&caller) {
+        runtime::revert(Error::NotGuardian);
+    }
+    let threshold = recovery.threshold as usize;
+    let request = recovery.request.get_or_insert(RecoveryRequest {
+        new_owner,
+        approvals: Vec::new(),
+        approved_at: None,
+    });
+    //The owner must cancel the pending recovery before the guardians approve another account
+    if request.new_owner != new_owner {
+        runtime::revert(Error::RecoveryConflict);
+    }
+    if !request.approvals.contains(&caller) {
+        request.approvals.push(caller);
+    }
+    if request.approved_at.is_none() && request.approvals.len() >= threshold {
+        request.approved_at = Some(u64::from(runtime::get_blocktime()));
+    }
+    write(named_key, recovery);
+}
+
+/**
+ * pub fn cancel - Cancel the pending recovery, the guardians are kept
+ *
+ * @return {type}  No return value
+ */
+pub fn cancel(named_key: &str) {
+    if let Some(mut recovery) = read(named_key) {
+        recovery.request = None;
+        write(named_key, recovery);
+    }
+}
+
+/**
+ * pub fn complete - Give the list to the approved account once the timelock is over.
+ * The new owner gets the owner role and the previous owner loses its role. The devices of the previous owner
+ * are removed, so the new owner can call the list with its own keys.
+ *
+ * @return {type}  No return value
+ */
+pub fn complete(named_key: &str) {
+    let mut recovery = read(named_key).unwrap_or_revert_with(Error::RecoveryNotReady);
+    let unlocked_at = recovery
+        .unlocked_at()
+        .unwrap_or_revert_with(Error::RecoveryNotReady);
+    if u64::from(runtime::get_blocktime()) < unlocked_at {
+        runtime::revert(Error::RecoveryNotReady);
+    }
+    let new_owner = recovery.request.take().unwrap_or_revert().new_owner;
+    acl::set_role(named_key, recovery.owner, None);
+    acl::set_role(named_key, new_owner, Some(Role::Owner));
+    devices::clear(named_key);
+    recovery.owner = new_owner;
+    write(named_key, recovery);
+}
@@ -0,0 +1,90 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::URef;
+use core::convert::TryInto;
+
+//Named key of the URef holding the names of the lists created by the contract
+pub const REGISTRY_KEY_NAME: &str = "blockchain_authenticator_lists";
+
+/**
+ * fn registry_uref - Get the URef of the registry, created on the first call
+ *
+ * @return {type}  The URef holding the names of the lists
+ */
+fn registry_uref() -> URef {
+    match runtime::get_key(REGISTRY_KEY_NAME) {
+        Some(key) => key.try_into().unwrap_or_revert(),
+        None => {
+            let empty_registry: Vec<String> = Vec::new();
+            let uref = storage::new_uref(empty_registry);
+            runtime::put_key(REGISTRY_KEY_NAME, uref.into());
+            uref
+        }
+    }
+}
+
+/**
+ * pub fn names - Get the names of the lists created by the contract
+ *
+ * @return {type}  The names, in the order of their creation
+ */
+pub fn names() -> Vec<String> {
+    match runtime::get_key(REGISTRY_KEY_NAME) {
+        Some(key) => {
+            let uref: URef = key.try_into().unwrap_or_revert();
+            storage::read(uref).unwrap_or_revert().unwrap_or_default()
+        }
+        None => Vec::new(),
+    }
+}
+
+/**
+ * pub fn register - Add a list to the registry if it isnt already registered
+ *
+ * @param  {type} named_key: &str Name of the list
+ * @return {type}                 No return value
+ */
+pub fn register(named_key: &str) {
+    let mut names = names();
+    if !names.iter().any(|name| name == named_key) {
+        names.push(String::from(named_key));
+        storage::write(registry_uref(), names);
+    }
+}
+
+/**
+ * pub fn unregister - Remove a list from the registry
+ *
+ * @param  {type} named_key: &str Name of the list
+ * @return {type}                 No return value
+ */
+pub fn unregister(named_key: &str) {
+    let mut names = names();
+    let length = names.len();
+    names.retain(|name| name != named_key);
+    if names.len() != length {
+        storage::write(registry_uref(), names);
+    }
+}
+
+/**
+ * pub fn rename - Replace the name of a list in the registry, keeping its position
+ *
+ * @param  {type} named_key: &str     Current name of the list
+ * @param  {type} new_named_key: &str New name of the list
+ * @return {type}                     No return value
+ */
+pub fn rename(named_key: &str, new_named_key: &str) {
+    let mut names = names();
+    match names.iter_mut().find(|name| name.as_str() == named_key) {
+        Some(name) => *name = String::from(new_named_key),
+        //A list created before the registry is registered under its new name
+        None => names.push(String::from(new_named_key)),
+    }
+    storage::write(registry_uref(), names);
+}
@@ -0,0 +1,173 @@
+use blockchain_authenticator_client::{OtpKind, SecretEntry};
+use blockchain_authenticator_envelope::{Envelope, EnvelopeError};
+
+use crate::error::Error;
+use crate::list::ID_PREFIX_MAX_LENGTH;
+
+//Maximum length of the key of a dictionary item
+const DICTIONARY_ITEM_KEY_MAX_LENGTH: usize = 64;
+//The ID is used in the keys of dictionary items after a prefix, so its length is limited
+pub const MAX_ID_LENGTH: usize = DICTIONARY_ITEM_KEY_MAX_LENGTH - ID_PREFIX_MAX_LENGTH;
+//Number of digits of the passwords supported by the authenticator apps
+pub const MIN_DIGITS: u8 = 6;
+pub const MAX_DIGITS: u8 = 8;
+
+/**
+ * pub struct SecretCode - An element of the list split into its ID and its VALUE
+ */
+pub struct SecretCode<'a> {
+    pub id: &'a str,
+    pub value: &'a str,
+}
+
+impl<'a> SecretCode<'a> {
+    /**
+     * pub fn split - Split a "[ID];[VALUE]" string. Everything after the first ";" belongs to the VALUE.
+     * A string without ";" is considered as an ID without VALUE, so the elements stored by the
+     * previous versions of the contract can still be read.
+     *
+     * @param  {type} secret_code: &str String where the content should be "[ID];[VALUE]"
+     * @return {type}                   The ID and the VALUE of the element
+     */
+    pub fn split(secret_code: &'a str) -> Self {
+        match secret_code.split_once(';') {
+            Some((id, value)) => SecretCode { id, value },
+            None => SecretCode {
+                id: secret_code,
+                value: "",
+            },
+        }
+    }
+
+    /**
+     * pub fn parse - Split a "[ID];[VALUE]" string received as parameter and check its content
+     *
+     * @param  {type} secret_code: &str String where the content should be "[ID];[VALUE]"
+     * @return {type}                   The ID and the VALUE of the element, or the reason why it is invalid
+     */
+    pub fn parse(secret_code: &'a str) -> Result<Self, Error> {
+        let parsed_secret_code = match secret_code.split_once(';') {
+            Some((id, value)) => SecretCode { id, value },
+            None => return Err(Error::MalformedEntry),
+        };
+        SecretCode::check_id(parsed_secret_code.id)?;
+        if parsed_secret_code.value.is_empty() {
+            return Err(Error::MalformedEntry);
+        }
+        Ok(parsed_secret_code)
+    }
+
+    /**
+     * pub fn parse_id - Get the ID of a string received as parameter to remove an element.
+     * Only the ID is needed, so both "[ID]" and "[ID];[VALUE]" are accepted.
+     *
+     * @param  {type} secret_code: &str String where the content should be "[ID]" or "[ID];[VALUE]"
+     * @return {type}                   The ID of the element, or the reason why it is invalid
+     */
+    pub fn parse_id(secret_code: &'a str) -> Result<&'a str, Error> {
+        let id = SecretCode::split(secret_code).id;
+        SecretCode::check_id(id)?;
+        Ok(id)
+    }
+
+    /**
+     * pub fn into_entry - Convert the element into the structured form stored by the contract
+     *
+     * @return {type}  The element with the default settings of the authenticator apps
+     */
+    pub fn into_entry(self) -> SecretEntry {
+        SecretEntry::new(self.id, self.value)
+    }
+
+    /**
+     * pub fn parse_uri - Convert an otpauth URI received as parameter, its label becomes the ID
+     *
+     * @param  {type} uri: &str "otpauth://[totp|hotp]/[label]?secret=[secret]&..."
+     * @return {type}           The element, or Error::InvalidUri
+     */
+    pub fn parse_uri(uri: &str) -> Result<SecretEntry, Error> {
+        SecretEntry::from_otpauth_uri(uri).map_err(|_| Error::InvalidUri)
+    }
+
+    /**
+     * pub fn check_entry - Check an element received in the structured form
+     *
+     * @param  {type} entry: &SecretEntry Element to check
+     * @return {type}                     Nothing, or the reason why the element is invalid
+     */
+    pub fn check_entry(entry: &SecretEntry) -> Result<(), Error> {
+        SecretCode::check_id(&entry.id)?;
+        //The ID must stay readable in the "[ID];[VALUE]" form
+        if entry.id.contains(';') || entry.secret.is_empty() {
+            return Err(Error::MalformedEntry);
+        }
+        if entry.digits < MIN_DIGITS || entry.digits > MAX_DIGITS {
+            return Err(Error::InvalidEntry);
+        }
+        if entry.kind == OtpKind::Totp && entry.period == 0 {
+            return Err(Error::InvalidEntry);
+        }
+        for tag in entry.tags.iter() {
+            SecretCode::check_tag(tag)?;
+        }
+        //An empty folder is the root of the list
+        if !entry.folder.is_empty() {
+            SecretCode::check_tag(&entry.folder)?;
+        }
+        Ok(())
+    }
+
+    /**
+     * pub fn check_tag - Check a tag or a folder path, each segment between "/" must not be empty
+     *
+     * @param  {type} tag: &str Tag or folder path to check
+     * @return {type}           Nothing, or Error::InvalidTag
+     */
+    pub fn check_tag(tag: &str) -> Result<(), Error> {
+        if tag.split('/').any(|segment| segment.is_empty()) {
+            return Err(Error::InvalidTag);
+        }
+        Ok(())
+    }
+
+    /**
+     * pub fn check_envelope - Check that a secret is an encrypted envelope "v1;[nonce];[ciphertext]".
+     * Only the structure is checked, the contract can't decrypt the secret.
+     *
+     * @param  {type} secret: &str Secret of an element
+     * @return {type}              Nothing, or the reason why the secret is not an envelope
+     */
+    pub fn check_envelope(secret: &str) -> Result<(), Error> {
+        match Envelope::parse(secret) {
+            Ok(_) => Ok(()),
+            Err(EnvelopeError::NotAnEnvelope) => Err(Error::PlaintextEntry),
+            Err(_) => Err(Error::MalformedEnvelope),
+        }
+    }
+
+    /**
+     * pub fn is_sealed - Check if a secret is encrypted, even with an envelope version that is not supported
+     *
+     * @param  {type} secret: &str Secret of an element
+     * @return {type}              true if the secret starts with a version prefix
+     */
+    pub fn is_sealed(secret: &str) -> bool {
+        !matches!(Envelope::parse(secret), Err(EnvelopeError::NotAnEnvelope))
+    }
+
+    /**
+     * pub fn check_id - Check that an ID can be saved as the key of a dictionary item
+     *
+     * @param  {type} id: &str ID to check
+     * @return {type}          Nothing, or the reason why the ID is invalid
+     */
+    pub fn check_id(id: &str) -> Result<(), Error> {
+        if id.is_empty() {
+            return Err(Error::EmptyId);
+        }
+        if id.len() > MAX_ID_LENGTH {
+            return Err(Error::IdTooLong);
+        }
+        Ok(())
+    }
+}
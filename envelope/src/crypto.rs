@@ -0,0 +1,77 @@
+use alloc::format;
+use alloc::string::String;
+
+use chacha20poly1305::aead::{Aead, NewAead, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+use crate::{hex, Envelope, EnvelopeError, NONCE_LENGTH, VERSION};
+
+const KEY_SALT: &[u8] = b"blockchain-authenticator";
+const KEY_INFO: &[u8] = b"entry-key v1";
+
+/// Key sealing and opening the entries of an account.
+pub struct EntryKey {
+    cipher: ChaCha20Poly1305,
+}
+
+impl EntryKey {
+    /// Derive the key of the entries from the secret key of the account (the raw bytes of its
+    /// Ed25519 or secp256k1 secret key).
+    pub fn derive(account_secret_key: &[u8]) -> Self {
+        let hkdf = Hkdf::<Sha256>::new(Some(KEY_SALT), account_secret_key);
+        let mut key = [0u8; 32];
+        hkdf.expand(KEY_INFO, &mut key)
+            .expect("32 bytes is a valid output length for HKDF-SHA256");
+        EntryKey {
+            cipher: ChaCha20Poly1305::new(Key::from_slice(&key)),
+        }
+    }
+
+    /// Encrypt a secret and return the entry "[ID];v1;[nonce];[ciphertext]" to send to the contract.
+    /// The ID is not encrypted, it must not reveal what the secret is for.
+    ///
+    /// The nonce must never be used twice with the same key, generate it with a secure random
+    /// number generator.
+    pub fn seal(&self, id: &str, secret: &str, nonce: &[u8; NONCE_LENGTH]) -> String {
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: secret.as_bytes(),
+                    aad: id.as_bytes(),
+                },
+            )
+            .expect("encryption with a valid key and nonce can't fail");
+        format!(
+            "{};{};{};{}",
+            id,
+            VERSION,
+            hex::encode(nonce),
+            hex::encode(&ciphertext)
+        )
+    }
+
+    /// Decrypt an entry read from the contract and return its ID and its secret.
+    pub fn open(&self, entry: &str) -> Result<(String, String), EnvelopeError> {
+        let (id, value) = entry.split_once(';').ok_or(EnvelopeError::MalformedEntry)?;
+        let envelope = Envelope::parse(value)?;
+        let nonce = hex::decode(envelope.nonce).ok_or(EnvelopeError::Malformed)?;
+        let ciphertext = hex::decode(envelope.ciphertext).ok_or(EnvelopeError::Malformed)?;
+
+        let secret = self
+            .cipher
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: id.as_bytes(),
+                },
+            )
+            .map_err(|_| EnvelopeError::Decryption)?;
+        let secret = String::from_utf8(secret).map_err(|_| EnvelopeError::Decryption)?;
+        Ok((String::from(id), secret))
+    }
+}
@@ -0,0 +1,39 @@
+#[cfg(feature = "crypto")]
+use alloc::{string::String, vec::Vec};
+
+#[cfg(feature = "crypto")]
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Check that a string is made of an even number of hex digits.
+pub fn is_hex(value: &str) -> bool {
+    value.len() & 1 == 0 && value.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Encode bytes as lowercase hex.
+#[cfg(feature = "crypto")]
+pub fn encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        encoded.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+        encoded.push(HEX_DIGITS[(byte & 0x0f) as usize] as char);
+    }
+    encoded
+}
+
+/// Decode hex, in lowercase or uppercase.
+#[cfg(feature = "crypto")]
+pub fn decode(value: &str) -> Option<Vec<u8>> {
+    if !is_hex(value) {
+        return None;
+    }
+    value
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| Some(digit_value(pair[0])? << 4 | digit_value(pair[1])?))
+        .collect()
+}
+
+#[cfg(feature = "crypto")]
+fn digit_value(digit: u8) -> Option<u8> {
+    (digit as char).to_digit(16).map(|value| value as u8)
+}
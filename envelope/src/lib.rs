@@ -0,0 +1,150 @@
+#![cfg_attr(not(test), no_std)]
+
+//! Encrypted entries of the Blockchain Authenticator lists.
+//!
+//! An encrypted entry keeps the "[ID];[VALUE]" format of the contract, its VALUE is the envelope :
+//!
+//! [ID];v1;[nonce];[ciphertext]
+//!
+//! - nonce : 12 random bytes, hex encoded
+//! - ciphertext : ChaCha20-Poly1305 encryption of the secret, authentication tag included, hex encoded.
+//!   The ID is used as associated data, so an envelope can't be moved under another ID.
+//!
+//! The encryption key is derived from the secret key of the account with HKDF-SHA256 (see `EntryKey`).
+//! The ID stays in plaintext in front of the envelope because the contract uses it as the key of the element.
+//!
+//! Only the secret is sealed : the ID and the other fields of a SecretEntry (issuer, account label, tags, folder,
+//! algorithm, digits, period, counter) are saved in plaintext, anyone can read them on chain.
+
+extern crate alloc;
+
+#[cfg(feature = "crypto")]
+mod crypto;
+mod hex;
+
+#[cfg(feature = "crypto")]
+pub use crypto::EntryKey;
+
+/// Version prefix of the envelopes created by this crate
+pub const VERSION: &str = "v1";
+/// Size of the nonce in bytes
+pub const NONCE_LENGTH: usize = 12;
+/// Size of the authentication tag appended to the ciphertext in bytes
+pub const TAG_LENGTH: usize = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EnvelopeError {
+    /// The value doesn't start with a version prefix, it is a plaintext value
+    NotAnEnvelope,
+    /// The version of the envelope is not supported
+    UnsupportedVersion,
+    /// The nonce or the ciphertext is missing or isn't hex of the expected length
+    Malformed,
+    /// The entry isn't formatted as "[ID];[VALUE]"
+    MalformedEntry,
+    /// The envelope can't be opened with this key : wrong key, wrong ID or modified ciphertext
+    Decryption,
+}
+
+/// The parts of an envelope, still hex encoded
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Envelope<'a> {
+    pub nonce: &'a str,
+    pub ciphertext: &'a str,
+}
+
+impl<'a> Envelope<'a> {
+    /// Check the structure of the VALUE of an entry, without decrypting it.
+    pub fn parse(value: &'a str) -> Result<Self, EnvelopeError> {
+        let (version, rest) = value.split_once(';').ok_or(EnvelopeError::NotAnEnvelope)?;
+        if !is_version(version) {
+            return Err(EnvelopeError::NotAnEnvelope);
+        }
+        if version != VERSION {
+            return Err(EnvelopeError::UnsupportedVersion);
+        }
+
+        let (nonce, ciphertext) = rest.split_once(';').ok_or(EnvelopeError::Malformed)?;
+        if nonce.len() != NONCE_LENGTH * 2 || !hex::is_hex(nonce) {
+            return Err(EnvelopeError::Malformed);
+        }
+        if ciphertext.len() < TAG_LENGTH * 2 || !hex::is_hex(ciphertext) {
+            return Err(EnvelopeError::Malformed);
+        }
+        Ok(Envelope { nonce, ciphertext })
+    }
+}
+
+/// A version prefix is "v" followed by digits.
+fn is_version(prefix: &str) -> bool {
+    prefix.len() > 1 && prefix.starts_with('v') && prefix[1..].bytes().all(|b| b.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "crypto")]
+    const ACCOUNT_SECRET_KEY: [u8; 32] = [7u8; 32];
+    #[cfg(feature = "crypto")]
+    const NONCE: [u8; NONCE_LENGTH] = [1u8; NONCE_LENGTH];
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn should_open_sealed_entry() {
+        let key = EntryKey::derive(&ACCOUNT_SECRET_KEY);
+        let entry = key.seal("ID1", "JBSWY3DPEHPK3PXP", &NONCE);
+
+        assert!(entry.starts_with("ID1;v1;010101010101010101010101;"));
+        assert_eq!(
+            key.open(&entry),
+            Ok((String::from("ID1"), String::from("JBSWY3DPEHPK3PXP")))
+        );
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn should_not_open_with_other_key() {
+        let entry = EntryKey::derive(&ACCOUNT_SECRET_KEY).seal("ID1", "JBSWY3DPEHPK3PXP", &NONCE);
+
+        assert_eq!(
+            EntryKey::derive(&[8u8; 32]).open(&entry),
+            Err(EnvelopeError::Decryption)
+        );
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn should_not_open_envelope_moved_to_other_id() {
+        let key = EntryKey::derive(&ACCOUNT_SECRET_KEY);
+        let entry = key.seal("ID1", "JBSWY3DPEHPK3PXP", &NONCE);
+        let moved_entry = entry.replacen("ID1", "ID2", 1);
+
+        assert_eq!(key.open(&moved_entry), Err(EnvelopeError::Decryption));
+    }
+
+    #[test]
+    fn should_check_envelope_structure() {
+        let ciphertext = "00".repeat(TAG_LENGTH);
+
+        assert!(
+            Envelope::parse(&format!("v1;{};{}", "ab".repeat(NONCE_LENGTH), ciphertext)).is_ok()
+        );
+        assert_eq!(
+            Envelope::parse("JBSWY3DPEHPK3PXP"),
+            Err(EnvelopeError::NotAnEnvelope)
+        );
+        assert_eq!(
+            Envelope::parse(&format!("v2;{};{}", "ab".repeat(NONCE_LENGTH), ciphertext)),
+            Err(EnvelopeError::UnsupportedVersion)
+        );
+        assert_eq!(
+            Envelope::parse(&format!("v1;abcd;{}", ciphertext)),
+            Err(EnvelopeError::Malformed)
+        );
+        assert_eq!(
+            Envelope::parse(&format!("v1;{};zz", "ab".repeat(NONCE_LENGTH))),
+            Err(EnvelopeError::Malformed)
+        );
+    }
+}
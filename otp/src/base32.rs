@@ -0,0 +1,40 @@
+use alloc::vec::Vec;
+
+/// Decode a base32 secret (RFC 4648 alphabet), as shown by the services and stored in the
+/// entries. The case, the spaces, the dashes and the padding are ignored. Trailing bits that don't
+/// make a full byte are dropped.
+pub fn decode(secret: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(secret.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for character in secret.bytes() {
+        let value = match character.to_ascii_uppercase() {
+            letter @ b'A'..=b'Z' => letter - b'A',
+            digit @ b'2'..=b'7' => digit - b'2' + 26,
+            b'=' | b' ' | b'-' => continue,
+            _ => return None,
+        };
+        buffer = (buffer << 5) | u32::from(value);
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_decode_rfc_4648_vectors() {
+        assert_eq!(decode("MY======").unwrap(), b"f");
+        assert_eq!(decode("MZXW6===").unwrap(), b"foo");
+        assert_eq!(decode("mzxw6ytboi").unwrap(), b"foobar");
+        assert_eq!(decode("MZXW 6YTB OI").unwrap(), b"foobar");
+        assert_eq!(decode("MZXW1"), None);
+    }
+}
@@ -0,0 +1,181 @@
+#![cfg_attr(not(test), no_std)]
+
+//! One-time passwords of the Blockchain Authenticator entries : HOTP (RFC 4226) and TOTP
+//! (RFC 6238), with SHA1, SHA256 or SHA512.
+//!
+//! `generate` reads a `SecretEntry` as stored by the contract, so a backend service can produce
+//! the current password of an element of a queried list. The secret must be in plaintext, an
+//! encrypted secret must be opened first with the envelope crate.
+
+extern crate alloc;
+
+pub mod base32;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use blockchain_authenticator_client::{Algorithm, OtpKind, SecretEntry};
+use blockchain_authenticator_envelope::Envelope;
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use sha2::{Sha256, Sha512};
+
+/// Largest number of digits of a password, the truncated HMAC has 31 bits
+pub const MAX_DIGITS: u8 = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OtpError {
+    /// The secret is an encrypted envelope, it must be opened first
+    EncryptedSecret,
+    /// The secret is empty or isn't base32
+    InvalidSecret,
+    /// The number of digits is 0 or more than MAX_DIGITS
+    InvalidDigits,
+    /// The period of a time-based password is 0
+    InvalidPeriod,
+}
+
+/// Counter-based password (RFC 4226).
+pub fn hotp(algorithm: Algorithm, key: &[u8], counter: u64, digits: u8) -> Result<u32, OtpError> {
+    if digits == 0 || digits > MAX_DIGITS {
+        return Err(OtpError::InvalidDigits);
+    }
+    let hash = match algorithm {
+        Algorithm::Sha1 => hmac::<Hmac<Sha1>>(key, &counter.to_be_bytes()),
+        Algorithm::Sha256 => hmac::<Hmac<Sha256>>(key, &counter.to_be_bytes()),
+        Algorithm::Sha512 => hmac::<Hmac<Sha512>>(key, &counter.to_be_bytes()),
+    };
+
+    //Dynamic truncation : the last 4 bits give the offset of 31 bits of the hash
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        hash[offset] & 0x7f,
+        hash[offset + 1],
+        hash[offset + 2],
+        hash[offset + 3],
+    ]);
+    Ok((u64::from(binary) % 10u64.pow(u32::from(digits))) as u32)
+}
+
+/// Time-based password (RFC 6238), the counter is the number of periods since the Unix epoch.
+pub fn totp(
+    algorithm: Algorithm,
+    key: &[u8],
+    unix_time: u64,
+    period: u32,
+    digits: u8,
+) -> Result<u32, OtpError> {
+    if period == 0 {
+        return Err(OtpError::InvalidPeriod);
+    }
+    hotp(algorithm, key, unix_time / u64::from(period), digits)
+}
+
+/// The password of an entry, padded with zeros to its number of digits. `unix_time` is in
+/// seconds and is only used by the time-based entries.
+pub fn generate(entry: &SecretEntry, unix_time: u64) -> Result<String, OtpError> {
+    if Envelope::parse(&entry.secret).is_ok() {
+        return Err(OtpError::EncryptedSecret);
+    }
+    let key = base32::decode(&entry.secret)
+        .filter(|key| !key.is_empty())
+        .ok_or(OtpError::InvalidSecret)?;
+
+    let code = match entry.kind {
+        OtpKind::Totp => totp(entry.algorithm, &key, unix_time, entry.period, entry.digits)?,
+        OtpKind::Hotp => hotp(entry.algorithm, &key, entry.counter, entry.digits)?,
+    };
+    Ok(format!("{:0width$}", code, width = entry.digits as usize))
+}
+
+fn hmac<M: Mac + hmac::digest::KeyInit>(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = <M as Mac>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHA1_SEED: &[u8] = b"12345678901234567890";
+    const SHA256_SEED: &[u8] = b"12345678901234567890123456789012";
+    const SHA512_SEED: &[u8] = b"1234567890123456789012345678901234567890123456789012345678901234";
+    // Base32 of SHA1_SEED
+    const SHA1_SEED_BASE32: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    #[test]
+    fn should_match_rfc_4226_vectors() {
+        let expected_codes = [
+            755224, 287082, 359152, 969429, 338314, 254676, 287922, 162583, 399871, 520489,
+        ];
+        for (counter, expected_code) in expected_codes.iter().enumerate() {
+            assert_eq!(
+                hotp(Algorithm::Sha1, SHA1_SEED, counter as u64, 6),
+                Ok(*expected_code),
+                "counter {}",
+                counter
+            );
+        }
+    }
+
+    #[test]
+    fn should_match_rfc_6238_vectors() {
+        let vectors = [
+            (59, 94287082, 46119246, 90693936),
+            (1111111109, 7081804, 68084774, 25091201),
+            (1111111111, 14050471, 67062674, 99943326),
+            (1234567890, 89005924, 91819424, 93441116),
+            (2000000000, 69279037, 90698825, 38618901),
+            (20000000000, 65353130, 77737706, 47863826),
+        ];
+        for (unix_time, sha1_code, sha256_code, sha512_code) in vectors.iter() {
+            assert_eq!(
+                totp(Algorithm::Sha1, SHA1_SEED, *unix_time, 30, 8),
+                Ok(*sha1_code)
+            );
+            assert_eq!(
+                totp(Algorithm::Sha256, SHA256_SEED, *unix_time, 30, 8),
+                Ok(*sha256_code)
+            );
+            assert_eq!(
+                totp(Algorithm::Sha512, SHA512_SEED, *unix_time, 30, 8),
+                Ok(*sha512_code)
+            );
+        }
+    }
+
+    #[test]
+    fn should_generate_code_of_entry() {
+        let totp_entry = SecretEntry {
+            digits: 8,
+            ..SecretEntry::new("ID1", SHA1_SEED_BASE32)
+        };
+        let hotp_entry = SecretEntry {
+            kind: OtpKind::Hotp,
+            counter: 1,
+            ..SecretEntry::new("ID2", SHA1_SEED_BASE32)
+        };
+
+        assert_eq!(
+            generate(&totp_entry, 1111111109),
+            Ok(String::from("07081804"))
+        );
+        assert_eq!(generate(&hotp_entry, 0), Ok(String::from("287082")));
+    }
+
+    #[test]
+    fn should_not_generate_code_of_encrypted_entry() {
+        let entry = SecretEntry::new(
+            "ID1",
+            "v1;000000000000000000000000;00000000000000000000000000000000",
+        );
+
+        assert_eq!(generate(&entry, 59), Err(OtpError::EncryptedSecret));
+        assert_eq!(
+            generate(&SecretEntry::new("ID1", "NOT BASE32 !"), 59),
+            Err(OtpError::InvalidSecret)
+        );
+    }
+}
@@ -1,7 +1,14 @@
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
     use std::path::PathBuf;
+    use std::sync::atomic::{AtomicU64, Ordering};
 
+    use blockchain_authenticator_client::{
+        history, Algorithm, AuthenticatorArgs, ChangeKind, ChangeRecord, Device, DeviceScope,
+        Operation, OtpKind, Role, SecretEntry, TrashedEntry, SHARED_ENTRY_POINT,
+    };
+    use blockchain_authenticator_envelope::{EntryKey, NONCE_LENGTH};
     use casper_engine_test_support::{
         DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT,
         DEFAULT_ACCOUNT_INITIAL_BALANCE, DEFAULT_GENESIS_CONFIG, DEFAULT_GENESIS_CONFIG_HASH,
@@ -10,13 +17,36 @@ mod tests {
     use casper_execution_engine::core::engine_state::{
         run_genesis_request::RunGenesisRequest, GenesisAccount,
     };
+    use casper_execution_engine::shared::additive_map::AdditiveMap;
+    use casper_execution_engine::shared::transform::Transform;
     use casper_types::{
-        account::AccountHash, runtime_args, CLValue, Key, Motes, PublicKey, RuntimeArgs, SecretKey,
-        StoredValue, U512,
+        account::AccountHash, bytesrepr::FromBytes, contracts::NamedKeys, runtime_args,
+        AccessRights, CLTyped, CLValue, ContractHash, Key, Motes, PublicKey, RuntimeArgs,
+        SecretKey, StoredValue, URef, U512,
     };
 
     const MY_ACCOUNT: [u8; 32] = [7u8; 32];
+    // Another account of the genesis, for the shared lists.
+    const OTHER_ACCOUNT: [u8; 32] = [8u8; 32];
+    // The new account of the owner of a shared list, for the recovery.
+    const RECOVERY_ACCOUNT: [u8; 32] = [9u8; 32];
     const CONTRACT_WASM: &str = "contract.wasm";
+    const INSTALLER_WASM: &str = "installer.wasm";
+    const CONTRACT_HASH_KEY_NAME: &str = "blockchain_authenticator";
+    const CONTRACT_VERSION_KEY_NAME: &str = "blockchain_authenticator_version";
+    const REGISTRY_KEY_NAME: &str = "blockchain_authenticator_lists";
+    // Longest ID accepted by the contract, 64 bytes of dictionary item key minus "trash:node:".
+    const MAX_ID_LENGTH: usize = 53;
+
+    static DEPLOY_COUNTER: AtomicU64 = AtomicU64::new(1);
+
+    // Each deploy needs its own hash, otherwise the URefs created by two deploys get the same address.
+    fn next_deploy_hash() -> [u8; 32] {
+        let mut deploy_hash = [0u8; 32];
+        deploy_hash[..8]
+            .copy_from_slice(&DEPLOY_COUNTER.fetch_add(1, Ordering::SeqCst).to_le_bytes());
+        deploy_hash
+    }
 
     fn setup(named_key: &str) -> InMemoryWasmTestBuilder {
         // Create keypair.
@@ -32,8 +62,24 @@ mod tests {
             None,
         );
 
+        let other_account = GenesisAccount::account(
+            PublicKey::from(&SecretKey::ed25519_from_bytes(OTHER_ACCOUNT).unwrap()),
+            Motes::new(U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE)),
+            None,
+        );
+
+        let recovery_account = GenesisAccount::account(
+            PublicKey::from(&SecretKey::ed25519_from_bytes(RECOVERY_ACCOUNT).unwrap()),
+            Motes::new(U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE)),
+            None,
+        );
+
         let mut genesis_config = DEFAULT_GENESIS_CONFIG.clone();
         genesis_config.ee_config_mut().push_account(account);
+        genesis_config.ee_config_mut().push_account(other_account);
+        genesis_config
+            .ee_config_mut()
+            .push_account(recovery_account);
 
         let run_genesis_request = RunGenesisRequest::new(
             *DEFAULT_GENESIS_CONFIG_HASH,
@@ -60,6 +106,7 @@ mod tests {
             .with_session_code(session_code, session_args)
             .with_authorization_keys(&[account_addr])
             .with_address(account_addr)
+            .with_deploy_hash(next_deploy_hash())
             .build();
 
         let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item).build();
@@ -95,9 +142,81 @@ mod tests {
             .with_session_code(session_code, session_args)
             .with_authorization_keys(&[account_addr])
             .with_address(account_addr)
+            .with_deploy_hash(next_deploy_hash())
+            .build();
+
+        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item).build();
+        builder.exec(execute_request).commit().expect_success();
+    }
+
+    fn call_contract_with_args(
+        builder: &mut InMemoryWasmTestBuilder,
+        account_addr: AccountHash,
+        session_args: RuntimeArgs,
+    ) {
+        let session_code = PathBuf::from(CONTRACT_WASM);
+
+        let deploy_item = DeployItemBuilder::new()
+            .with_empty_payment_bytes(runtime_args! {
+                ARG_AMOUNT => *DEFAULT_PAYMENT
+            })
+            .with_session_code(session_code, session_args)
+            .with_authorization_keys(&[account_addr])
+            .with_address(account_addr)
+            .with_deploy_hash(next_deploy_hash())
+            .build();
+
+        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item).build();
+        builder.exec(execute_request).commit().expect_success();
+    }
+
+    // Run a deploy that must be reverted, and return its error, so a test can try several calls.
+    fn call_contract_with_args_error(
+        builder: &mut InMemoryWasmTestBuilder,
+        account_addr: AccountHash,
+        session_args: RuntimeArgs,
+    ) -> String {
+        let session_code = PathBuf::from(CONTRACT_WASM);
+
+        let deploy_item = DeployItemBuilder::new()
+            .with_empty_payment_bytes(runtime_args! {
+                ARG_AMOUNT => *DEFAULT_PAYMENT
+            })
+            .with_session_code(session_code, session_args)
+            .with_authorization_keys(&[account_addr])
+            .with_address(account_addr)
+            .with_deploy_hash(next_deploy_hash())
             .build();
 
         let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item).build();
+        builder.exec(execute_request).commit().expect_failure();
+        builder
+            .get_error()
+            .expect("deploy should be reverted")
+            .to_string()
+    }
+
+    fn call_contract_at(
+        builder: &mut InMemoryWasmTestBuilder,
+        account_addr: AccountHash,
+        session_args: RuntimeArgs,
+        block_time: u64,
+    ) {
+        let session_code = PathBuf::from(CONTRACT_WASM);
+
+        let deploy_item = DeployItemBuilder::new()
+            .with_empty_payment_bytes(runtime_args! {
+                ARG_AMOUNT => *DEFAULT_PAYMENT
+            })
+            .with_session_code(session_code, session_args)
+            .with_authorization_keys(&[account_addr])
+            .with_address(account_addr)
+            .with_deploy_hash(next_deploy_hash())
+            .build();
+
+        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item)
+            .with_block_time(block_time)
+            .build();
         builder.exec(execute_request).commit().expect_success();
     }
 
@@ -121,21 +240,150 @@ mod tests {
             .with_session_code(session_code, session_args)
             .with_authorization_keys(&[account_addr])
             .with_address(account_addr)
+            .with_deploy_hash(next_deploy_hash())
             .build();
 
         let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item).build();
         builder.exec(execute_request).commit().expect_success();
     }
-    #[test]
-    fn should_create_named_keys() {
-        let named_key = "my-named-key";
-        let builder = setup(named_key);
-        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
-        let public_key = PublicKey::from(&secret_key);
 
-        // Create an AccountHash from a public key.
-        let account_addr = AccountHash::from(&public_key);
+    // Save a list the way the first version of the contract did: all the elements in a single URef.
+    fn seed_legacy_list(
+        builder: &mut InMemoryWasmTestBuilder,
+        account_addr: AccountHash,
+        named_key: &str,
+        secret_codes: Vec<&str>,
+    ) {
+        let uref = URef::new(next_deploy_hash(), AccessRights::READ_ADD_WRITE);
+        let secret_codes: Vec<String> = secret_codes.into_iter().map(String::from).collect();
+        let mut named_keys = NamedKeys::new();
+        named_keys.insert(String::from(named_key), Key::URef(uref));
+
+        let mut effects = AdditiveMap::new();
+        effects.insert(
+            Key::URef(uref),
+            Transform::Write(StoredValue::CLValue(CLValue::from_t(secret_codes).unwrap())),
+        );
+        effects.insert(Key::Account(account_addr), Transform::AddKeys(named_keys));
+        let pre_state_hash = builder.get_post_state_hash();
+        builder.commit_transforms(pre_state_hash, effects);
+    }
+
+    fn install_contract(builder: &mut InMemoryWasmTestBuilder, account_addr: AccountHash) {
+        let session_code = PathBuf::from(INSTALLER_WASM);
+
+        let deploy_item = DeployItemBuilder::new()
+            .with_empty_payment_bytes(runtime_args! {
+                ARG_AMOUNT => *DEFAULT_PAYMENT
+            })
+            .with_session_code(session_code, runtime_args! {})
+            .with_authorization_keys(&[account_addr])
+            .with_address(account_addr)
+            .with_deploy_hash(next_deploy_hash())
+            .build();
+
+        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item).build();
+        builder.exec(execute_request).commit().expect_success();
+    }
+
+    fn call_stored_contract(
+        builder: &mut InMemoryWasmTestBuilder,
+        account_addr: AccountHash,
+        args: AuthenticatorArgs,
+    ) {
+        let deploy_item = DeployItemBuilder::new()
+            .with_empty_payment_bytes(runtime_args! {
+                ARG_AMOUNT => *DEFAULT_PAYMENT
+            })
+            .with_stored_session_named_key(
+                CONTRACT_HASH_KEY_NAME,
+                args.entry_point(),
+                args.entry_point_args(),
+            )
+            .with_authorization_keys(&[account_addr])
+            .with_address(account_addr)
+            .with_deploy_hash(next_deploy_hash())
+            .build();
+
+        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item).build();
+        builder.exec(execute_request).commit().expect_success();
+    }
+
+    // Call the "shared" entry point of the contract installed by the owner, as another account.
+    fn call_shared_contract(
+        builder: &mut InMemoryWasmTestBuilder,
+        account_addr: AccountHash,
+        contract_hash: ContractHash,
+        args: AuthenticatorArgs,
+    ) {
+        let deploy_item = DeployItemBuilder::new()
+            .with_empty_payment_bytes(runtime_args! {
+                ARG_AMOUNT => *DEFAULT_PAYMENT
+            })
+            .with_stored_session_hash(contract_hash, SHARED_ENTRY_POINT, args.session_args())
+            .with_authorization_keys(&[account_addr])
+            .with_address(account_addr)
+            .with_deploy_hash(next_deploy_hash())
+            .build();
+
+        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item).build();
+        builder.exec(execute_request).commit().expect_success();
+    }
+
+    fn call_shared_contract_at(
+        builder: &mut InMemoryWasmTestBuilder,
+        account_addr: AccountHash,
+        contract_hash: ContractHash,
+        args: AuthenticatorArgs,
+        block_time: u64,
+    ) {
+        let deploy_item = DeployItemBuilder::new()
+            .with_empty_payment_bytes(runtime_args! {
+                ARG_AMOUNT => *DEFAULT_PAYMENT
+            })
+            .with_stored_session_hash(contract_hash, SHARED_ENTRY_POINT, args.session_args())
+            .with_authorization_keys(&[account_addr])
+            .with_address(account_addr)
+            .with_deploy_hash(next_deploy_hash())
+            .build();
+
+        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item)
+            .with_block_time(block_time)
+            .build();
+        builder.exec(execute_request).commit().expect_success();
+    }
+
+    fn get_contract_hash(
+        builder: &InMemoryWasmTestBuilder,
+        account_addr: AccountHash,
+    ) -> ContractHash {
+        ContractHash::new(
+            get_named_key(builder, account_addr, CONTRACT_HASH_KEY_NAME)
+                .into_hash()
+                .expect("should be hash"),
+        )
+    }
+
+    fn get_contract_named_key(
+        builder: &InMemoryWasmTestBuilder,
+        contract_hash: ContractHash,
+        named_key: &str,
+    ) -> Option<Key> {
+        builder
+            .query(None, Key::Hash(contract_hash.value()), &[])
+            .expect("should query contract")
+            .as_contract()
+            .expect("should be contract")
+            .named_keys()
+            .get(named_key)
+            .copied()
+    }
 
+    fn get_named_key(
+        builder: &InMemoryWasmTestBuilder,
+        account_addr: AccountHash,
+        named_key: &str,
+    ) -> Key {
         //get account
         let account = builder
             .query(None, Key::Account(account_addr), &[])
@@ -144,22 +392,293 @@ mod tests {
             .cloned()
             .expect("should be account");
 
-        let retvaluekey = *(account
+        *(account
             .named_keys()
             .get(named_key)
-            .expect("named key should exist"));
+            .expect("named key should exist"))
+    }
+
+    fn has_named_key(
+        builder: &InMemoryWasmTestBuilder,
+        account_addr: AccountHash,
+        named_key: &str,
+    ) -> bool {
+        builder
+            .query(None, Key::Account(account_addr), &[])
+            .expect("should query account")
+            .as_account()
+            .expect("should be account")
+            .named_keys()
+            .contains_key(named_key)
+    }
+
+    fn get_named_key_value(
+        builder: &InMemoryWasmTestBuilder,
+        account_addr: AccountHash,
+        named_key: &str,
+    ) -> StoredValue {
+        let retvaluekey = get_named_key(builder, account_addr, named_key);
 
-        let retvalue = builder
+        builder
             .query(None, retvaluekey, &[])
-            .expect("Value should exist");
+            .expect("Value should exist")
+    }
+
+    fn get_dictionary_item<T: CLTyped + FromBytes>(
+        builder: &InMemoryWasmTestBuilder,
+        dictionary: URef,
+        item_key: &str,
+    ) -> Option<T> {
+        builder
+            .query_dictionary_item(None, dictionary, item_key)
+            .ok()
+            .map(|value| {
+                value
+                    .as_cl_value()
+                    .cloned()
+                    .expect("should be CLValue")
+                    .into_t()
+                    .expect("should have the expected type")
+            })
+    }
+
+    // Read the elements of a list in the order of its index, as "ID;VALUE" strings.
+    fn get_secret_codes(
+        builder: &InMemoryWasmTestBuilder,
+        account_addr: AccountHash,
+        named_key: &str,
+    ) -> Vec<String> {
+        let entries = get_named_key(builder, account_addr, named_key)
+            .into_uref()
+            .expect("should be uref");
+        let meta = get_named_key(builder, account_addr, &format!("{}_meta", named_key))
+            .into_uref()
+            .expect("should be uref");
+        read_secret_codes(builder, entries, meta)
+    }
+
+    // Read the elements of a shared list, saved in the named keys of the contract.
+    fn get_shared_secret_codes(
+        builder: &InMemoryWasmTestBuilder,
+        contract_hash: ContractHash,
+        named_key: &str,
+    ) -> Vec<String> {
+        let get_uref = |name: &str| {
+            get_contract_named_key(builder, contract_hash, name)
+                .expect("named key should exist")
+                .into_uref()
+                .expect("should be uref")
+        };
+        read_secret_codes(
+            builder,
+            get_uref(named_key),
+            get_uref(&format!("{}_meta", named_key)),
+        )
+    }
+
+    // Read the IDs of an index of the meta dictionary, following the links from the first ID.
+    fn read_index(builder: &InMemoryWasmTestBuilder, meta: URef, namespace: &str) -> Vec<String> {
+        let length: u64 =
+            get_dictionary_item(builder, meta, &format!("{}length", namespace)).unwrap_or_default();
+        let mut ids = Vec::new();
+        let mut id: String =
+            get_dictionary_item(builder, meta, &format!("{}first", namespace)).unwrap_or_default();
+        while !id.is_empty() {
+            let (_previous, next): (String, String) =
+                get_dictionary_item::<Option<(String, String)>>(
+                    builder,
+                    meta,
+                    &format!("{}node:{}", namespace, id),
+                )
+                .flatten()
+                .expect("ID should be linked");
+            ids.push(id);
+            id = next;
+        }
+        assert_eq!(ids.len() as u64, length, "The length should match the IDs");
+        ids
+    }
+
+    fn read_secret_codes(
+        builder: &InMemoryWasmTestBuilder,
+        entries: URef,
+        meta: URef,
+    ) -> Vec<String> {
+        read_index(builder, meta, "ids:")
+            .into_iter()
+            .map(|id| {
+                get_dictionary_item::<Option<SecretEntry>>(builder, entries, &id)
+                    .flatten()
+                    .expect("element should exist")
+                    .to_legacy()
+            })
+            .collect()
+    }
+
+    fn get_entry(
+        builder: &InMemoryWasmTestBuilder,
+        account_addr: AccountHash,
+        named_key: &str,
+        id: &str,
+    ) -> SecretEntry {
+        let entries = get_named_key(builder, account_addr, named_key)
+            .into_uref()
+            .expect("should be uref");
+        get_dictionary_item::<Option<SecretEntry>>(builder, entries, id)
+            .flatten()
+            .expect("element should exist")
+    }
+
+    fn get_counter(
+        builder: &InMemoryWasmTestBuilder,
+        account_addr: AccountHash,
+        named_key: &str,
+        id: &str,
+    ) -> u64 {
+        get_entry(builder, account_addr, named_key, id).counter
+    }
+
+    fn get_list_version(
+        builder: &InMemoryWasmTestBuilder,
+        account_addr: AccountHash,
+        named_key: &str,
+    ) -> u64 {
+        let meta = get_named_key(builder, account_addr, &format!("{}_meta", named_key))
+            .into_uref()
+            .expect("should be uref");
+        get_dictionary_item(builder, meta, "version").unwrap_or_default()
+    }
+
+    // Read the trashed elements of a list in the order of the index of the trash.
+    fn get_trashed_entries(
+        builder: &InMemoryWasmTestBuilder,
+        account_addr: AccountHash,
+        named_key: &str,
+    ) -> Vec<TrashedEntry> {
+        let meta = get_named_key(builder, account_addr, &format!("{}_meta", named_key))
+            .into_uref()
+            .expect("should be uref");
+
+        read_index(builder, meta, "trash:")
+            .into_iter()
+            .map(|id| {
+                get_dictionary_item::<Option<TrashedEntry>>(
+                    builder,
+                    meta,
+                    &format!("trashed:{}", id),
+                )
+                .flatten()
+                .expect("trashed element should exist")
+            })
+            .collect()
+    }
+
+    // Read the history of the changes of a list.
+    fn get_history(
+        builder: &InMemoryWasmTestBuilder,
+        account_addr: AccountHash,
+        named_key: &str,
+    ) -> Vec<ChangeRecord> {
+        let meta = get_named_key(builder, account_addr, &format!("{}_meta", named_key))
+            .into_uref()
+            .expect("should be uref");
+
+        let length: u64 = get_dictionary_item(builder, meta, "history:length").unwrap_or_default();
+        (0..length)
+            .map(|position| {
+                get_dictionary_item(builder, meta, &format!("history:{}", position))
+                    .expect("record should exist")
+            })
+            .collect()
+    }
+
+    // Read the events written by the last deploy, ordered by version, then by type and ID.
+    fn get_last_events(builder: &InMemoryWasmTestBuilder) -> Vec<BTreeMap<String, String>> {
+        let transforms = builder.get_transforms();
+        let mut events: Vec<BTreeMap<String, String>> = transforms
+            .last()
+            .expect("should have transforms")
+            .iter()
+            .filter_map(|(_key, transform)| match transform {
+                Transform::Write(StoredValue::CLValue(cl_value))
+                    if *cl_value.cl_type() == BTreeMap::<String, String>::cl_type() =>
+                {
+                    cl_value.clone().into_t().ok()
+                }
+                _ => None,
+            })
+            .collect();
+        events.sort_by_key(|event| {
+            (
+                event["version"].parse::<u64>().unwrap(),
+                event["event_type"].clone(),
+                event.get("id").cloned(),
+            )
+        });
+        events
+    }
+
+    fn event(
+        event_type: &str,
+        named_key: &str,
+        id: Option<&str>,
+        version: u64,
+    ) -> BTreeMap<String, String> {
+        let mut event = BTreeMap::new();
+        event.insert(
+            String::from("event_type"),
+            format!("blockchain_authenticator_{}", event_type),
+        );
+        event.insert(String::from("named_key"), String::from(named_key));
+        if let Some(id) = id {
+            event.insert(String::from("id"), String::from(id));
+        }
+        event.insert(String::from("version"), version.to_string());
+        event
+    }
+
+    // Read the devices allowed to call a list.
+    fn get_devices(
+        builder: &InMemoryWasmTestBuilder,
+        account_addr: AccountHash,
+        named_key: &str,
+    ) -> Vec<Device> {
+        get_named_key_value(builder, account_addr, &format!("{}_devices", named_key))
+            .as_cl_value()
+            .cloned()
+            .expect("should be CLValue")
+            .into_t()
+            .expect("should be a list of devices")
+    }
+
+    // Read the names of the lists saved in the registry of the contract.
+    fn get_registered_lists(
+        builder: &InMemoryWasmTestBuilder,
+        account_addr: AccountHash,
+    ) -> Vec<String> {
+        get_named_key_value(builder, account_addr, REGISTRY_KEY_NAME)
+            .as_cl_value()
+            .cloned()
+            .expect("should be CLValue")
+            .into_t()
+            .expect("should be a list of strings")
+    }
+
+    #[test]
+    fn should_create_named_keys() {
+        let named_key = "my-named-key";
+        let builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        let retvalue = get_secret_codes(&builder, account_addr, named_key);
 
         // make assertions
         let expected_output: Vec<&str> = Vec::new();
-        assert_eq!(
-            retvalue,
-            StoredValue::CLValue(CLValue::from_t(expected_output).unwrap()),
-            "Value should be empty"
-        );
+        assert_eq!(retvalue, expected_output, "Value should be empty");
     }
 
     #[test]
@@ -188,38 +707,19 @@ mod tests {
         let account_addr = AccountHash::from(&public_key);
 
         let mut data: Vec<&str> = Vec::new();
-        data.push("TEST");
+        data.push("ID1;TEST");
 
         let mut builder = setup(named_key);
 
         call_contract(&mut builder, account_addr, named_key, data, "add");
 
-        //get account
-        let account = builder
-            .query(None, Key::Account(account_addr), &[])
-            .expect("should query account")
-            .as_account()
-            .cloned()
-            .expect("should be account");
-
-        let retvaluekey = *(account
-            .named_keys()
-            .get(named_key)
-            .expect("named key should exist"));
-
-        let retvalue = builder
-            .query(None, retvaluekey, &[])
-            .expect("Value should exist");
+        let retvalue = get_secret_codes(&builder, account_addr, named_key);
 
         //
         // // make assertions
         let mut expected_output: Vec<&str> = Vec::new();
-        expected_output.push("TEST");
-        assert_eq!(
-            retvalue,
-            StoredValue::CLValue(CLValue::from_t(expected_output).unwrap()),
-            "Should contain 1 element"
-        );
+        expected_output.push("ID1;TEST");
+        assert_eq!(retvalue, expected_output, "Should contain 1 element");
     }
 
     #[test]
@@ -240,22 +740,7 @@ mod tests {
 
         call_contract(&mut builder, account_addr, named_key, data, "add");
 
-        //get account
-        let account = builder
-            .query(None, Key::Account(account_addr), &[])
-            .expect("should query account")
-            .as_account()
-            .cloned()
-            .expect("should be account");
-
-        let retvaluekey = *(account
-            .named_keys()
-            .get(named_key)
-            .expect("named key should exist"));
-
-        let retvalue = builder
-            .query(None, retvaluekey, &[])
-            .expect("Value should exist");
+        let retvalue = get_secret_codes(&builder, account_addr, named_key);
 
         //
         // // make assertions
@@ -264,11 +749,7 @@ mod tests {
         expected_output.push("ID2;VALUE");
         expected_output.push("ID3;VALUE");
 
-        assert_eq!(
-            retvalue,
-            StoredValue::CLValue(CLValue::from_t(expected_output).unwrap()),
-            "Should contain 3 elements"
-        );
+        assert_eq!(retvalue, expected_output, "Should contain 3 elements");
     }
 
     #[test]
@@ -292,32 +773,13 @@ mod tests {
 
         call_contract(&mut builder, account_addr, named_key, data_call_two, "add");
 
-        //get account
-        let account = builder
-            .query(None, Key::Account(account_addr), &[])
-            .expect("should query account")
-            .as_account()
-            .cloned()
-            .expect("should be account");
-
-        let retvaluekey = *(account
-            .named_keys()
-            .get(named_key)
-            .expect("named key should exist"));
-
-        let retvalue = builder
-            .query(None, retvaluekey, &[])
-            .expect("Value should exist");
+        let retvalue = get_secret_codes(&builder, account_addr, named_key);
 
         //
         // // make assertions
         let mut expected_output: Vec<&str> = Vec::new();
         expected_output.push("ID1;VALUE2");
-        assert_eq!(
-            retvalue,
-            StoredValue::CLValue(CLValue::from_t(expected_output).unwrap()),
-            "Should be updated"
-        );
+        assert_eq!(retvalue, expected_output, "Should be updated");
     }
 
     #[test]
@@ -337,31 +799,12 @@ mod tests {
         call_contract(&mut builder, account_addr, named_key, data_to_add, "add");
         call_contract(&mut builder, account_addr, named_key, data_to_remove, "del");
 
-        //get account
-        let account = builder
-            .query(None, Key::Account(account_addr), &[])
-            .expect("should query account")
-            .as_account()
-            .cloned()
-            .expect("should be account");
-
-        let retvaluekey = *(account
-            .named_keys()
-            .get(named_key)
-            .expect("named key should exist"));
-
-        let retvalue = builder
-            .query(None, retvaluekey, &[])
-            .expect("Value should exist");
+        let retvalue = get_secret_codes(&builder, account_addr, named_key);
 
         //
         // // make assertions
         let expected_output: Vec<&str> = Vec::new();
-        assert_eq!(
-            retvalue,
-            StoredValue::CLValue(CLValue::from_t(expected_output).unwrap()),
-            "Value should be empty"
-        );
+        assert_eq!(retvalue, expected_output, "Value should be empty");
     }
 
     #[test]
@@ -385,32 +828,13 @@ mod tests {
         call_contract(&mut builder, account_addr, named_key, data_to_add, "add");
         call_contract(&mut builder, account_addr, named_key, data_to_remove, "del");
 
-        //get account
-        let account = builder
-            .query(None, Key::Account(account_addr), &[])
-            .expect("should query account")
-            .as_account()
-            .cloned()
-            .expect("should be account");
-
-        let retvaluekey = *(account
-            .named_keys()
-            .get(named_key)
-            .expect("named key should exist"));
-
-        let retvalue = builder
-            .query(None, retvaluekey, &[])
-            .expect("Value should exist");
+        let retvalue = get_secret_codes(&builder, account_addr, named_key);
 
         //
         // // make assertions
         let mut expected_output: Vec<&str> = Vec::new();
         expected_output.push("ID3;VALUE");
-        assert_eq!(
-            retvalue,
-            StoredValue::CLValue(CLValue::from_t(expected_output).unwrap()),
-            "Value should be empty"
-        );
+        assert_eq!(retvalue, expected_output, "Value should be empty");
     }
 
     #[test]
@@ -434,32 +858,13 @@ mod tests {
 
         call_contract(&mut builder, account_addr, named_key, data_call_two, "del");
 
-        //get account
-        let account = builder
-            .query(None, Key::Account(account_addr), &[])
-            .expect("should query account")
-            .as_account()
-            .cloned()
-            .expect("should be account");
-
-        let retvaluekey = *(account
-            .named_keys()
-            .get(named_key)
-            .expect("named key should exist"));
-
-        let retvalue = builder
-            .query(None, retvaluekey, &[])
-            .expect("Value should exist");
+        let retvalue = get_secret_codes(&builder, account_addr, named_key);
 
         //
         // // make assertions
         let mut expected_output: Vec<&str> = Vec::new();
         expected_output.push("ID1;VALUE");
-        assert_eq!(
-            retvalue,
-            StoredValue::CLValue(CLValue::from_t(expected_output).unwrap()),
-            "Value should contain 1 element"
-        );
+        assert_eq!(retvalue, expected_output, "Value should contain 1 element");
     }
 
     #[test]
@@ -479,62 +884,3246 @@ mod tests {
 
         call_contract(&mut builder, account_addr, named_key, data_call, "add");
 
-        //get account
-        let account = builder
-            .query(None, Key::Account(account_addr), &[])
-            .expect("should query account")
-            .as_account()
-            .cloned()
-            .expect("should be account");
-
-        let retvaluekey = *(account
-            .named_keys()
-            .get(named_key)
-            .expect("named key should exist"));
-
-        let retvalue = builder
-            .query(None, retvaluekey, &[])
-            .expect("Value should exist");
+        let retvalue = get_secret_codes(&builder, account_addr, named_key);
 
         //
         // // make assertions
         let mut expected_output: Vec<&str> = Vec::new();
         expected_output.push("ID1;VALUE");
         expected_output.push("ID2;VALUE");
-        assert_eq!(
-            retvalue,
-            StoredValue::CLValue(CLValue::from_t(expected_output).unwrap()),
-            "Value should contain 2 elements"
-        );
+        assert_eq!(retvalue, expected_output, "Value should contain 2 elements");
 
         let data_call: Vec<&str> = Vec::new();
 
         call_contract(&mut builder, account_addr, named_key, data_call, "delall");
 
-        //get account
-        let account = builder
-            .query(None, Key::Account(account_addr), &[])
-            .expect("should query account")
-            .as_account()
-            .cloned()
-            .expect("should be account");
-
-        let retvaluekey = *(account
-            .named_keys()
-            .get(named_key)
-            .expect("named key should exist"));
-
-        let retvalue = builder
-            .query(None, retvaluekey, &[])
-            .expect("Value should exist");
+        let retvalue = get_secret_codes(&builder, account_addr, named_key);
 
         //
         // // make assertions
         let expected_output: Vec<&str> = Vec::new();
+        assert_eq!(retvalue, expected_output, "Value should contain 0 elements");
+    }
+
+    #[test]
+    fn should_del_exact_id_only() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        let data_to_add: Vec<&str> = vec!["ID10;VALUE", "ID1;VALUE"];
+        let data_to_remove: Vec<&str> = vec!["ID1;VALUE"];
+        call_contract(&mut builder, account_addr, named_key, data_to_add, "add");
+        call_contract(&mut builder, account_addr, named_key, data_to_remove, "del");
+
+        // make assertions
+        let expected_output: Vec<&str> = vec!["ID10;VALUE"];
         assert_eq!(
-            retvalue,
-            StoredValue::CLValue(CLValue::from_t(expected_output).unwrap()),
-            "Value should contain 0 elements"
+            get_secret_codes(&builder, account_addr, named_key),
+            expected_output,
+            "ID10 should not be removed"
+        );
+    }
+
+    #[test]
+    fn should_not_match_id_in_value() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;ID2"],
+            "add",
+        );
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID2;VALUE"],
+            "add",
+        );
+
+        // make assertions
+        let expected_output: Vec<&str> = vec!["ID1;ID2", "ID2;VALUE"];
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, named_key),
+            expected_output,
+            "ID1 should not be replaced"
+        );
+    }
+
+    #[test]
+    fn should_migrate_without_losing_elements() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        let data_to_add: Vec<&str> = vec!["ID1;VALUE", "ID10;VALUE", "ID2;ID1"];
+        call_contract(&mut builder, account_addr, named_key, data_to_add, "add");
+        call_contract(&mut builder, account_addr, named_key, Vec::new(), "migrate");
+
+        // make assertions
+        let expected_output: Vec<&str> = vec!["ID1;VALUE", "ID10;VALUE", "ID2;ID1"];
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, named_key),
+            expected_output,
+            "Value should contain 3 elements"
+        );
+    }
+
+    #[test]
+    fn should_migrate_legacy_list() {
+        let named_key = "my-named-key";
+        let legacy_named_key = "my-legacy-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        seed_legacy_list(
+            &mut builder,
+            account_addr,
+            legacy_named_key,
+            vec!["ID1;A", "ID10;B", "ID1;C"],
+        );
+        call_contract(
+            &mut builder,
+            account_addr,
+            legacy_named_key,
+            Vec::new(),
+            "migrate",
+        );
+
+        // make assertions
+        let expected_output: Vec<&str> = vec!["ID1;C", "ID10;B"];
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, legacy_named_key),
+            expected_output,
+            "The last element of ID1 should be kept at its first position"
+        );
+        let legacy_secret_codes: Vec<String> = get_named_key_value(
+            &builder,
+            account_addr,
+            &format!("{}_legacy", legacy_named_key),
+        )
+        .as_cl_value()
+        .cloned()
+        .expect("should be CLValue")
+        .into_t()
+        .expect("should be a list of strings");
+        assert_eq!(
+            legacy_secret_codes,
+            vec!["ID1;A", "ID10;B", "ID1;C"],
+            "The legacy list should be kept unchanged"
+        );
+    }
+
+    #[test]
+    fn should_remove_all_elements_of_legacy_list_in_place() {
+        let named_key = "my-named-key";
+        let legacy_named_key = "my-legacy-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        seed_legacy_list(
+            &mut builder,
+            account_addr,
+            legacy_named_key,
+            vec!["ID1;VALUE1", "ID2;VALUE2"],
+        );
+        let key_before = get_named_key(&builder, account_addr, legacy_named_key);
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::delall(legacy_named_key)
+                .with_force(true)
+                .session_args(),
+        );
+
+        // make assertions
+        assert_eq!(
+            get_last_events(&builder),
+            vec![event("cleared", legacy_named_key, None, 0)]
+        );
+        assert_eq!(
+            get_named_key(&builder, account_addr, legacy_named_key),
+            key_before,
+            "The same URef should be used"
+        );
+        let secret_codes: Vec<String> =
+            get_named_key_value(&builder, account_addr, legacy_named_key)
+                .as_cl_value()
+                .cloned()
+                .expect("should be CLValue")
+                .into_t()
+                .expect("should be a list of strings");
+        assert!(secret_codes.is_empty(), "The old URef should be emptied");
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(1) [65537]")]
+    fn should_panic_unknown_method() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE"],
+            "dellall",
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(2) [65538]")]
+    fn should_panic_malformed_entry() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(&mut builder, account_addr, named_key, vec!["TEST"], "add");
+    }
+
+    #[test]
+    fn should_add_id_of_max_length() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        let secret_code = format!("{};VALUE", "I".repeat(MAX_ID_LENGTH));
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec![secret_code.as_str()],
+            "add",
+        );
+
+        // make assertions
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, named_key),
+            vec![secret_code],
+            "Value should contain the ID of max length"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(6) [65542]")]
+    fn should_panic_id_too_long() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        let secret_code = format!("{};VALUE", "I".repeat(MAX_ID_LENGTH + 1));
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec![secret_code.as_str()],
+            "add",
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(3) [65539]")]
+    fn should_panic_empty_id() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(&mut builder, account_addr, named_key, vec![";VALUE"], "add");
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(4) [65540]")]
+    fn should_panic_duplicate_id() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE", "ID2;VALUE", "ID1;VALUE2"],
+            "add",
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(5) [65541]")]
+    fn should_panic_missing_list() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            "other-named-key",
+            vec!["ID1;VALUE"],
+            "del",
+        );
+    }
+
+    #[test]
+    fn should_install_stored_contract() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        install_contract(&mut builder, account_addr);
+
+        //get account
+        let account = builder
+            .query(None, Key::Account(account_addr), &[])
+            .expect("should query account")
+            .as_account()
+            .cloned()
+            .expect("should be account");
+
+        for name in [
+            "blockchain_authenticator_package",
+            "blockchain_authenticator_access",
+            CONTRACT_HASH_KEY_NAME,
+            CONTRACT_VERSION_KEY_NAME,
+        ] {
+            assert!(
+                account.named_keys().contains_key(name),
+                "named key {} should exist",
+                name
+            );
+        }
+
+        // make assertions
+        assert_eq!(
+            get_named_key_value(&builder, account_addr, CONTRACT_VERSION_KEY_NAME),
+            StoredValue::CLValue(CLValue::from_t(1u32).unwrap()),
+            "First version should be installed"
+        );
+    }
+
+    #[test]
+    fn should_upgrade_stored_contract() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        install_contract(&mut builder, account_addr);
+        install_contract(&mut builder, account_addr);
+
+        // make assertions
+        assert_eq!(
+            get_named_key_value(&builder, account_addr, CONTRACT_VERSION_KEY_NAME),
+            StoredValue::CLValue(CLValue::from_t(2u32).unwrap()),
+            "Second version should be installed"
+        );
+    }
+
+    #[test]
+    fn should_add_and_del_with_stored_contract() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        install_contract(&mut builder, account_addr);
+
+        call_stored_contract(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::add(named_key, &["ID1;VALUE", "ID2;VALUE"]),
+        );
+        call_stored_contract(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::del(named_key, &["ID1"]),
+        );
+
+        // make assertions
+        let expected_output: Vec<&str> = vec!["ID2;VALUE"];
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, named_key),
+            expected_output,
+            "Value should contain 1 element"
+        );
+    }
+
+    #[test]
+    fn should_store_elements_by_id() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE", "ID2;VALUE2"],
+            "add",
+        );
+        call_contract(&mut builder, account_addr, named_key, vec!["ID1"], "del");
+
+        let entries = get_named_key(&builder, account_addr, named_key)
+            .into_uref()
+            .expect("should be uref");
+
+        // make assertions
+        assert_eq!(
+            get_dictionary_item::<Option<SecretEntry>>(&builder, entries, "ID2")
+                .flatten()
+                .map(|entry| entry.secret),
+            Some(String::from("VALUE2")),
+            "ID2 should be readable by its ID"
+        );
+        assert_eq!(
+            get_dictionary_item::<Option<SecretEntry>>(&builder, entries, "ID1"),
+            Some(None),
+            "ID1 should be emptied"
+        );
+    }
+
+    #[test]
+    fn should_add_with_constant_cost() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID00;VALUE"],
+            "add",
+        );
+        let small_list_cost = builder.last_exec_gas_cost().value();
+
+        let ids: Vec<String> = (1..50)
+            .map(|index| format!("ID{:02};VALUE", index))
+            .collect();
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            ids.iter().map(String::as_str).collect(),
+            "add",
+        );
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID50;VALUE"],
+            "add",
+        );
+        let large_list_cost = builder.last_exec_gas_cost().value();
+
+        // make assertions
+        assert!(
+            large_list_cost * U512::from(100) < small_list_cost * U512::from(105),
+            "Adding to a list of 50 elements should cost the same as adding to a list of 1 element"
+        );
+    }
+
+    #[test]
+    fn should_remove_all_elements_in_place() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE", "ID2;VALUE"],
+            "add",
+        );
+        let entries_before = get_named_key(&builder, account_addr, named_key);
+
+        call_contract(&mut builder, account_addr, named_key, Vec::new(), "delall");
+
+        let entries = get_named_key(&builder, account_addr, named_key);
+        let meta = get_named_key(&builder, account_addr, &format!("{}_meta", named_key))
+            .into_uref()
+            .expect("should be uref");
+
+        // make assertions
+        assert_eq!(entries, entries_before, "The same URef should be used");
+        let entries = entries.into_uref().expect("should be uref");
+        for id in ["ID1", "ID2"] {
+            assert_eq!(
+                get_dictionary_item::<Option<SecretEntry>>(&builder, entries, id),
+                Some(None),
+                "The old value of {} should be erased",
+                id
+            );
+        }
+        for id in ["ID1", "ID2"] {
+            assert_eq!(
+                get_dictionary_item::<Option<(String, String)>>(
+                    &builder,
+                    meta,
+                    &format!("ids:node:{}", id)
+                ),
+                Some(None),
+                "{} should be unlinked from the index",
+                id
+            );
+        }
+        assert!(read_index(&builder, meta, "ids:").is_empty());
+    }
+
+    #[test]
+    fn should_purge_ids_with_delall() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE", "ID2;VALUE"],
+            "add",
+        );
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::delall(named_key)
+                .with_purge(true)
+                .session_args(),
+        );
+
+        let entries = get_named_key(&builder, account_addr, named_key)
+            .into_uref()
+            .expect("should be uref");
+        let meta = get_named_key(&builder, account_addr, &format!("{}_meta", named_key))
+            .into_uref()
+            .expect("should be uref");
+
+        // make assertions
+        let expected_output: Vec<&str> = Vec::new();
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, named_key),
+            expected_output,
+            "Value should contain 0 elements"
+        );
+        for id in ["ID1", "ID2"] {
+            assert_eq!(
+                get_dictionary_item::<Option<SecretEntry>>(&builder, entries, id),
+                Some(None),
+                "The old value of {} should be erased",
+                id
+            );
+        }
+        for item in ["ids:first", "ids:last"] {
+            assert_eq!(
+                get_dictionary_item::<String>(&builder, meta, item),
+                Some(String::new()),
+                "The ID saved in {} should be erased",
+                item
+            );
+        }
+        for id in ["ID1", "ID2"] {
+            assert_eq!(
+                get_dictionary_item::<Option<(String, String)>>(
+                    &builder,
+                    meta,
+                    &format!("ids:node:{}", id)
+                ),
+                Some(None),
+                "The links of {} should be erased",
+                id
+            );
+        }
+        let length: u64 = get_dictionary_item(&builder, meta, "history:length").unwrap_or_default();
+        assert!(length > 0, "The changes should be recorded");
+        for position in 0..length {
+            let record_bytes = builder
+                .query_dictionary_item(None, meta, &format!("history:{}", position))
+                .expect("record should exist")
+                .as_cl_value()
+                .expect("should be CLValue")
+                .inner_bytes()
+                .clone();
+            assert!(
+                !record_bytes
+                    .windows(b"VALUE".len())
+                    .any(|window| window == b"VALUE"),
+                "The record history:{} should not hold the secret",
+                position
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(9) [65545]")]
+    fn should_panic_plaintext_entry_in_strict_list() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::configure(named_key)
+                .with_strict(true)
+                .session_args(),
+        );
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE"],
+            "add",
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(9) [65545]")]
+    fn should_panic_restore_plaintext_entry_in_strict_list() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE1"],
+            "add",
+        );
+        call_contract(&mut builder, account_addr, named_key, vec!["ID1"], "del");
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::configure(named_key)
+                .with_strict(true)
+                .session_args(),
+        );
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::restore(named_key, &["ID1"]).session_args(),
+        );
+    }
+
+    #[test]
+    fn should_add_envelope_in_strict_list() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        // 12 bytes of nonce, then a ciphertext made of a 4 bytes secret and the 16 bytes tag
+        let envelope = format!("ID1;v1;{};{}", "00".repeat(12), "ab".repeat(20));
+
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::configure(named_key)
+                .with_strict(true)
+                .session_args(),
+        );
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec![envelope.as_str()],
+            "add",
+        );
+
+        // make assertions
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, named_key),
+            vec![envelope.as_str()],
+            "Value should contain the envelope"
+        );
+    }
+
+    #[test]
+    fn should_add_secret_entries() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        let entry = SecretEntry {
+            issuer: String::from("Casper"),
+            account_label: String::from("alice@example.com"),
+            algorithm: Algorithm::Sha256,
+            digits: 8,
+            ..SecretEntry::new("ID1", "VALUE;WITH;SEPARATORS")
+        };
+        let updated_entry = SecretEntry {
+            secret: String::from("VALUE2"),
+            ..entry.clone()
+        };
+
+        call_contract_at(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::add_entries(named_key, &[entry.clone()]).session_args(),
+            1_000,
+        );
+        call_contract_at(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::add(named_key, &["ID2;VALUE"])
+                .with_secret_entries(&[updated_entry.clone()])
+                .session_args(),
+            2_000,
+        );
+
+        let entries = get_named_key(&builder, account_addr, named_key)
+            .into_uref()
+            .expect("should be uref");
+
+        // make assertions
+        assert_eq!(
+            get_dictionary_item::<Option<SecretEntry>>(&builder, entries, "ID1").flatten(),
+            Some(SecretEntry {
+                created_at: 1_000,
+                updated_at: 2_000,
+                ..updated_entry
+            }),
+            "The update should keep the creation date"
+        );
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, named_key),
+            vec!["ID1;VALUE2", "ID2;VALUE"],
+            "Value should contain 2 elements"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(11) [65547]")]
+    fn should_panic_invalid_entry() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        let entry = SecretEntry {
+            digits: 4,
+            ..SecretEntry::new("ID1", "VALUE")
+        };
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::add_entries(named_key, &[entry]).session_args(),
+        );
+    }
+
+    #[test]
+    fn should_remove_tagged_elements() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        let entries = [
+            SecretEntry {
+                tags: vec![String::from("old-job")],
+                ..SecretEntry::new("ID1", "VALUE1")
+            },
+            SecretEntry {
+                folder: String::from("old-job/servers"),
+                ..SecretEntry::new("ID2", "VALUE2")
+            },
+            SecretEntry {
+                tags: vec![String::from("work")],
+                ..SecretEntry::new("ID3", "VALUE3")
+            },
+        ];
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::add(named_key, &["ID4;VALUE4"])
+                .with_secret_entries(&entries)
+                .session_args(),
+        );
+        // ID1 doesn't carry the tag "work", it stays in the list
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::del(named_key, &["ID1", "ID3"])
+                .with_tag("work")
+                .session_args(),
+        );
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::delall(named_key)
+                .with_tag("old-job")
+                .session_args(),
+        );
+
+        // make assertions
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, named_key),
+            vec!["ID4;VALUE4"],
+            "Only the element without tag should stay"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(28) [65564]")]
+    fn should_panic_invalid_tag() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        let entry = SecretEntry {
+            folder: String::from("work//servers"),
+            ..SecretEntry::new("ID1", "VALUE")
+        };
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::add_entries(named_key, &[entry]).session_args(),
+        );
+    }
+
+    #[test]
+    fn should_add_otpauth_uris() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::add_uris(
+                named_key,
+                &["otpauth://totp/Example:alice%40google.com?secret=JBSWY3DPEHPK3PXP&issuer=Example"],
+            )
+            .session_args(),
+        );
+
+        let entries = get_named_key(&builder, account_addr, named_key)
+            .into_uref()
+            .expect("should be uref");
+
+        // make assertions
+        let entry = get_dictionary_item::<Option<SecretEntry>>(
+            &builder,
+            entries,
+            "Example:alice@google.com",
+        )
+        .flatten()
+        .expect("The label should be the ID");
+        assert_eq!(entry.issuer, "Example");
+        assert_eq!(entry.account_label, "alice@google.com");
+        assert_eq!(entry.secret, "JBSWY3DPEHPK3PXP");
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(12) [65548]")]
+    fn should_panic_invalid_uri() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::add_uris(named_key, &["otpauth://totp/alice?issuer=Example"])
+                .session_args(),
+        );
+    }
+
+    #[test]
+    fn should_generate_code_of_stored_entry() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        // Secret of the RFC 6238 test vectors, in base32
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ"],
+            "add",
+        );
+
+        let entries = get_named_key(&builder, account_addr, named_key)
+            .into_uref()
+            .expect("should be uref");
+        let entry = get_dictionary_item::<Option<SecretEntry>>(&builder, entries, "ID1")
+            .flatten()
+            .expect("element should exist");
+
+        // make assertions
+        assert_eq!(
+            blockchain_authenticator_otp::generate(&entry, 59),
+            Ok(String::from("287082")),
+            "The stored element should generate the RFC code"
+        );
+    }
+
+    #[test]
+    fn should_increment_and_resync_counter() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        let entry = SecretEntry {
+            kind: OtpKind::Hotp,
+            counter: 5,
+            ..SecretEntry::new("ID1", "JBSWY3DPEHPK3PXP")
+        };
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::add_entries(named_key, &[entry]).session_args(),
+        );
+
+        // make assertions
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::increment(named_key, "ID1").session_args(),
+        );
+        assert_eq!(get_counter(&builder, account_addr, named_key, "ID1"), 6);
+
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::resync(named_key, "ID1", 3).session_args(),
+        );
+        assert_eq!(
+            get_counter(&builder, account_addr, named_key, "ID1"),
+            6,
+            "The counter should never move back"
+        );
+
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::resync(named_key, "ID1", 10).session_args(),
+        );
+        assert_eq!(get_counter(&builder, account_addr, named_key, "ID1"), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(13) [65549]")]
+    fn should_panic_increment_missing_entry() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::increment(named_key, "ID1").session_args(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(14) [65550]")]
+    fn should_panic_increment_time_based_entry() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;JBSWY3DPEHPK3PXP"],
+            "add",
+        );
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::increment(named_key, "ID1").session_args(),
+        );
+    }
+    #[test]
+    fn should_register_created_lists() {
+        let mut builder = setup("first-list");
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            "first-list",
+            vec!["ID1;VALUE1"],
+            "add",
+        );
+        call_contract(
+            &mut builder,
+            account_addr,
+            "second-list",
+            vec!["ID1;VALUE1"],
+            "add",
+        );
+        call_contract(
+            &mut builder,
+            account_addr,
+            "first-list",
+            vec!["ID2;VALUE2"],
+            "add",
+        );
+
+        // make assertions
+        assert_eq!(
+            get_registered_lists(&builder, account_addr),
+            vec!["first-list", "second-list"]
+        );
+    }
+
+    #[test]
+    fn should_rename_list() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE1", "ID2;VALUE2"],
+            "add",
+        );
+        call_contract_at(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::rename(named_key, "renamed-key").session_args(),
+            5_000,
+        );
+
+        // make assertions
+        assert!(!has_named_key(&builder, account_addr, named_key));
+        assert!(!has_named_key(&builder, account_addr, "my-named-key_meta"));
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, "renamed-key"),
+            vec!["ID1;VALUE1", "ID2;VALUE2"]
+        );
+        assert_eq!(
+            get_registered_lists(&builder, account_addr),
+            vec!["renamed-key"]
+        );
+        assert_eq!(get_list_version(&builder, account_addr, "renamed-key"), 2);
+        assert_eq!(
+            get_history(&builder, account_addr, "renamed-key").last(),
+            Some(&ChangeRecord {
+                version: 2,
+                kind: ChangeKind::Renamed,
+                id: String::from(named_key),
+                block_time: 5_000,
+                caller: account_addr,
+                digest: None,
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(15) [65551]")]
+    fn should_panic_rename_to_existing_list() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE1"],
+            "add",
+        );
+        call_contract(
+            &mut builder,
+            account_addr,
+            "other-key",
+            vec!["ID2;VALUE2"],
+            "add",
+        );
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::rename(named_key, "other-key").session_args(),
+        );
+    }
+
+    #[test]
+    fn should_copy_list() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE1", "ID2;VALUE2"],
+            "add",
+        );
+        call_contract(
+            &mut builder,
+            account_addr,
+            "other-key",
+            vec!["ID2;OLD", "ID3;VALUE3"],
+            "add",
+        );
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::pin(named_key, "ID1", true).session_args(),
+        );
+        call_contract_at(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::copy(named_key, "other-key").session_args(),
+            5_000,
+        );
+
+        // make assertions
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, named_key),
+            vec!["ID1;VALUE1", "ID2;VALUE2"]
+        );
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, "other-key"),
+            vec!["ID2;VALUE2", "ID3;VALUE3", "ID1;VALUE1"]
+        );
+        for id in ["ID1", "ID2"] {
+            let entry = get_entry(&builder, account_addr, "other-key", id);
+            assert_eq!(
+                (entry.created_at, entry.updated_at, entry.pinned),
+                (5_000, 5_000, false),
+                "{} should be dated with the time of the copy",
+                id
+            );
+        }
+    }
+
+    #[test]
+    fn should_delete_list() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE1"],
+            "add",
+        );
+        let entries = get_named_key(&builder, account_addr, named_key)
+            .into_uref()
+            .expect("should be uref");
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::dellist(named_key).session_args(),
+        );
+
+        // make assertions
+        assert!(!has_named_key(&builder, account_addr, named_key));
+        assert!(!has_named_key(&builder, account_addr, "my-named-key_meta"));
+        assert_eq!(
+            get_dictionary_item::<Option<SecretEntry>>(&builder, entries, "ID1"),
+            Some(None)
+        );
+        assert!(get_registered_lists(&builder, account_addr).is_empty());
+    }
+    #[test]
+    fn should_apply_batch_operations() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE1", "ID2;VALUE2", "ID3;VALUE3", "ID4;VALUE4"],
+            "add",
+        );
+        let operations = [
+            Operation::Add(SecretEntry::new("ID5", "VALUE5")),
+            Operation::Upsert(SecretEntry::new("ID1", "NEW")),
+            Operation::Delete(String::from("ID2")),
+            Operation::RenameId {
+                id: String::from("ID3"),
+                new_id: String::from("ID6"),
+                secret: None,
+            },
+            Operation::Move {
+                id: String::from("ID4"),
+                named_key: String::from("other-key"),
+            },
+        ];
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::batch(named_key, &operations).session_args(),
+        );
+
+        // make assertions
+        let mut secret_codes = get_secret_codes(&builder, account_addr, named_key);
+        secret_codes.sort();
+        assert_eq!(secret_codes, vec!["ID1;NEW", "ID5;VALUE5", "ID6;VALUE3"]);
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, "other-key"),
+            vec!["ID4;VALUE4"]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(13) [65549]")]
+    fn should_panic_batch_with_invalid_operation() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE1"],
+            "add",
+        );
+        //The deletion of a missing element reverts the addition made before it
+        let operations = [
+            Operation::Add(SecretEntry::new("ID2", "VALUE2")),
+            Operation::Delete(String::from("ID3")),
+        ];
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::batch(named_key, &operations).session_args(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(4) [65540]")]
+    fn should_panic_batch_add_existing_id() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE1"],
+            "add",
+        );
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::batch(named_key, &[Operation::Add(SecretEntry::new("ID1", "NEW"))])
+                .session_args(),
+        );
+    }
+
+    #[test]
+    fn should_increment_version_of_other_list_once_per_batch() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE1", "ID2;VALUE2"],
+            "add",
+        );
+        call_contract(
+            &mut builder,
+            account_addr,
+            "other-key",
+            vec!["ID3;VALUE3"],
+            "add",
+        );
+        let operations = [
+            Operation::Move {
+                id: String::from("ID1"),
+                named_key: String::from("other-key"),
+            },
+            Operation::Move {
+                id: String::from("ID2"),
+                named_key: String::from("other-key"),
+            },
+        ];
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::batch(named_key, &operations).session_args(),
+        );
+
+        // make assertions
+        assert_eq!(get_list_version(&builder, account_addr, named_key), 2);
+        assert_eq!(get_list_version(&builder, account_addr, "other-key"), 2);
+        assert_eq!(
+            get_last_events(&builder),
+            vec![
+                event("added", "other-key", Some("ID1"), 2),
+                event("added", "other-key", Some("ID2"), 2),
+                event("deleted", named_key, Some("ID1"), 2),
+                event("deleted", named_key, Some("ID2"), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_rename_sealed_entry_with_its_new_envelope() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        let key = EntryKey::derive(&MY_ACCOUNT);
+        let entry = key.seal("ID1", "JBSWY3DPEHPK3PXP", &[1u8; NONCE_LENGTH]);
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec![entry.as_str()],
+            "add",
+        );
+        let renamed_entry = key.seal("ID2", "JBSWY3DPEHPK3PXP", &[2u8; NONCE_LENGTH]);
+        let (_id, secret) = renamed_entry.split_once(';').unwrap();
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::batch(
+                named_key,
+                &[Operation::RenameId {
+                    id: String::from("ID1"),
+                    new_id: String::from("ID2"),
+                    secret: Some(String::from(secret)),
+                }],
+            )
+            .session_args(),
+        );
+
+        // make assertions
+        let secret_codes = get_secret_codes(&builder, account_addr, named_key);
+        assert_eq!(secret_codes, vec![renamed_entry]);
+        assert_eq!(
+            key.open(&secret_codes[0]),
+            Ok((String::from("ID2"), String::from("JBSWY3DPEHPK3PXP")))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(30) [65566]")]
+    fn should_panic_rename_sealed_entry_without_new_envelope() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        let entry =
+            EntryKey::derive(&MY_ACCOUNT).seal("ID1", "JBSWY3DPEHPK3PXP", &[1u8; NONCE_LENGTH]);
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec![entry.as_str()],
+            "add",
+        );
+        //The envelope of ID1 could never be opened under ID2
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::batch(
+                named_key,
+                &[Operation::RenameId {
+                    id: String::from("ID1"),
+                    new_id: String::from("ID2"),
+                    secret: None,
+                }],
+            )
+            .session_args(),
+        );
+    }
+    #[test]
+    fn should_increment_version_on_each_write() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE1", "ID2;VALUE2"],
+            "add",
+        );
+        assert_eq!(get_list_version(&builder, account_addr, named_key), 1);
+
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::del(named_key, &["ID1"])
+                .with_expected_version(1)
+                .session_args(),
+        );
+
+        // make assertions
+        assert_eq!(get_list_version(&builder, account_addr, named_key), 2);
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, named_key),
+            vec!["ID2;VALUE2"]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(16) [65552]")]
+    fn should_panic_version_conflict() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        //Both devices read the version 1
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE1"],
+            "add",
+        );
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::add(named_key, &["ID1;FIRST-DEVICE"])
+                .with_expected_version(1)
+                .session_args(),
+        );
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::add(named_key, &["ID1;SECOND-DEVICE"])
+                .with_expected_version(1)
+                .session_args(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(18) [65554]")]
+    fn should_panic_forbidden_before_version_conflict() {
+        let named_key = "team-list";
+        let mut builder = setup("my-named-key");
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+        let other_account_addr = AccountHash::from(&PublicKey::from(
+            &SecretKey::ed25519_from_bytes(OTHER_ACCOUNT).unwrap(),
+        ));
+
+        install_contract(&mut builder, account_addr);
+        let contract_hash = get_contract_hash(&builder, account_addr);
+
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::add(named_key, &["ID1;VALUE1"]),
+        );
+        // A wrong version doesnt tell an account without role that the list changed
+        call_shared_contract(
+            &mut builder,
+            other_account_addr,
+            contract_hash,
+            AuthenticatorArgs::get(named_key).with_expected_version(0),
+        );
+    }
+    #[test]
+    fn should_insert_and_update_elements() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::insert(named_key, &["ID1;VALUE1", "ID2;VALUE2"]).session_args(),
+        );
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::update(named_key, &["ID2;NEW"]).session_args(),
+        );
+
+        // make assertions
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, named_key),
+            vec!["ID1;VALUE1", "ID2;NEW"]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(4) [65540]")]
+    fn should_panic_insert_existing_id() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE1"],
+            "add",
+        );
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::insert(named_key, &["ID1;OVERWRITTEN"]).session_args(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(13) [65549]")]
+    fn should_panic_update_missing_id() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE1"],
+            "add",
+        );
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::update(named_key, &["ID2;VALUE2"]).session_args(),
+        );
+    }
+    #[test]
+    fn should_move_deleted_elements_to_trash_and_restore() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE1", "ID2;VALUE2"],
+            "add",
+        );
+        call_contract_at(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::del(named_key, &["ID1"]).session_args(),
+            10_000,
+        );
+
+        // make assertions
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, named_key),
+            vec!["ID2;VALUE2"]
+        );
+        let trashed_entries = get_trashed_entries(&builder, account_addr, named_key);
+        assert_eq!(trashed_entries.len(), 1);
+        assert_eq!(trashed_entries[0].entry.to_legacy(), "ID1;VALUE1");
+        assert_eq!(trashed_entries[0].deleted_at, 10_000);
+
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::restore(named_key, &["ID1"]).session_args(),
+        );
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, named_key),
+            vec!["ID2;VALUE2", "ID1;VALUE1"]
+        );
+        assert!(get_trashed_entries(&builder, account_addr, named_key).is_empty());
+    }
+
+    #[test]
+    fn should_trash_restore_and_purge_id_of_max_length() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        let id = "I".repeat(MAX_ID_LENGTH);
+        let secret_code = format!("{};VALUE", id);
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec![secret_code.as_str()],
+            "add",
+        );
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::del(named_key, &[id.as_str()]).session_args(),
+        );
+
+        // make assertions
+        let trashed_entries = get_trashed_entries(&builder, account_addr, named_key);
+        assert_eq!(trashed_entries.len(), 1);
+        assert_eq!(trashed_entries[0].entry.id, id);
+
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::restore(named_key, &[id.as_str()]).session_args(),
+        );
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, named_key),
+            vec![secret_code]
+        );
+        assert!(get_trashed_entries(&builder, account_addr, named_key).is_empty());
+
+        call_contract(&mut builder, account_addr, named_key, Vec::new(), "delall");
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::purge(named_key).session_args(),
+        );
+        assert!(get_secret_codes(&builder, account_addr, named_key).is_empty());
+        assert!(get_trashed_entries(&builder, account_addr, named_key).is_empty());
+    }
+
+    #[test]
+    fn should_trash_all_elements_without_force() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE1", "ID2;VALUE2"],
+            "add",
+        );
+        call_contract(&mut builder, account_addr, named_key, Vec::new(), "delall");
+
+        // make assertions
+        let trashed_ids: Vec<String> = get_trashed_entries(&builder, account_addr, named_key)
+            .into_iter()
+            .map(|trashed_entry| trashed_entry.entry.id)
+            .collect();
+        assert_eq!(trashed_ids, vec!["ID1", "ID2"]);
+
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::delall(named_key)
+                .with_force(true)
+                .session_args(),
+        );
+        assert_eq!(
+            get_trashed_entries(&builder, account_addr, named_key).len(),
+            2,
+            "Without purge the trash is kept"
+        );
+    }
+
+    #[test]
+    fn should_purge_trash_after_retention() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::configure(named_key)
+                .with_retention(1_000)
+                .session_args(),
+        );
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE1"],
+            "add",
+        );
+        call_contract_at(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::del(named_key, &["ID1"]).session_args(),
+            10_000,
+        );
+
+        // make assertions
+        call_contract_at(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::purge(named_key).session_args(),
+            10_500,
+        );
+        assert_eq!(
+            get_trashed_entries(&builder, account_addr, named_key).len(),
+            1,
+            "The element should be retained"
+        );
+
+        call_contract_at(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::purge(named_key).session_args(),
+            11_000,
+        );
+        assert!(get_trashed_entries(&builder, account_addr, named_key).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(17) [65553]")]
+    fn should_panic_purge_retained_element() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::configure(named_key)
+                .with_retention(1_000)
+                .session_args(),
+        );
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE1"],
+            "add",
+        );
+        call_contract_at(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::del(named_key, &["ID1"]).session_args(),
+            10_000,
+        );
+        call_contract_at(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::purge(named_key)
+                .with_entries(&["ID1"])
+                .session_args(),
+            10_500,
+        );
+    }
+    #[test]
+    fn should_record_history_of_changes() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract_at(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::add(named_key, &["ID1;VALUE1"]).session_args(),
+            1_000,
+        );
+        call_contract_at(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::add(named_key, &["ID1;NEW", "ID2;VALUE2"]).session_args(),
+            2_000,
+        );
+        call_contract_at(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::del(named_key, &["ID2"]).session_args(),
+            3_000,
+        );
+
+        // make assertions
+        let records = get_history(&builder, account_addr, named_key);
+        let changes: Vec<(u64, ChangeKind, &str, u64)> = records
+            .iter()
+            .map(|record| {
+                (
+                    record.version,
+                    record.kind,
+                    record.id.as_str(),
+                    record.block_time,
+                )
+            })
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (1, ChangeKind::Added, "ID1", 1_000),
+                (2, ChangeKind::Updated, "ID1", 2_000),
+                (2, ChangeKind::Added, "ID2", 2_000),
+                (3, ChangeKind::Deleted, "ID2", 3_000),
+            ]
+        );
+        assert!(records.iter().all(|record| record.caller == account_addr));
+
+        let state_at_version_2 = history::state_at(&records, 2);
+        let entries = get_named_key(&builder, account_addr, named_key)
+            .into_uref()
+            .expect("should be uref");
+        let id1 = get_dictionary_item::<Option<SecretEntry>>(&builder, entries, "ID1")
+            .flatten()
+            .expect("element should exist");
+        assert_eq!(
+            state_at_version_2.keys().collect::<Vec<&String>>(),
+            vec!["ID1", "ID2"]
+        );
+        assert_eq!(
+            state_at_version_2.get("ID1"),
+            Some(&history::entry_digest(&id1).unwrap())
+        );
+    }
+
+    #[test]
+    fn should_emit_events_for_the_changes_of_the_list() {
+        let named_key = "my-named-key";
+        let legacy_named_key = "my-legacy-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::configure(named_key)
+                .with_retention(1_000)
+                .session_args(),
+        );
+
+        // make assertions
+        let version = get_list_version(&builder, account_addr, named_key);
+        assert_eq!(
+            get_last_events(&builder),
+            vec![event("configured", named_key, None, version)]
+        );
+
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::rename(named_key, "renamed-key").session_args(),
+        );
+        let mut renamed = event("renamed", "renamed-key", None, version + 1);
+        renamed.insert(String::from("previous_named_key"), String::from(named_key));
+        assert_eq!(get_last_events(&builder), vec![renamed]);
+
+        seed_legacy_list(
+            &mut builder,
+            account_addr,
+            legacy_named_key,
+            vec!["ID1;VALUE1"],
+        );
+        call_contract(
+            &mut builder,
+            account_addr,
+            legacy_named_key,
+            Vec::new(),
+            "migrate",
+        );
+        assert_eq!(
+            get_last_events(&builder),
+            vec![
+                event("added", legacy_named_key, Some("ID1"), 1),
+                event("migrated", legacy_named_key, None, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_emit_events_for_each_change() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract_at(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::add(named_key, &["ID1;VALUE1"]).session_args(),
+            1_000,
+        );
+        // make assertions
+        assert_eq!(
+            get_last_events(&builder),
+            vec![event("added", named_key, Some("ID1"), 1)]
+        );
+
+        call_contract_at(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::add(named_key, &["ID1;NEW", "ID2;VALUE2"]).session_args(),
+            2_000,
+        );
+        assert_eq!(
+            get_last_events(&builder),
+            vec![
+                event("added", named_key, Some("ID2"), 2),
+                event("updated", named_key, Some("ID1"), 2),
+            ]
+        );
+
+        call_contract_at(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::del(named_key, &["ID2"]).session_args(),
+            3_000,
+        );
+        assert_eq!(
+            get_last_events(&builder),
+            vec![event("deleted", named_key, Some("ID2"), 3)]
+        );
+
+        call_contract_at(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::delall(named_key).session_args(),
+            4_000,
+        );
+        assert_eq!(
+            get_last_events(&builder),
+            vec![
+                event("cleared", named_key, None, 4),
+                event("deleted", named_key, Some("ID1"), 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_share_list_between_accounts() {
+        let named_key = "team-list";
+        let mut builder = setup("my-named-key");
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+        let other_account_addr = AccountHash::from(&PublicKey::from(
+            &SecretKey::ed25519_from_bytes(OTHER_ACCOUNT).unwrap(),
+        ));
+
+        install_contract(&mut builder, account_addr);
+        let contract_hash = get_contract_hash(&builder, account_addr);
+
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::add(named_key, &["ID1;VALUE1"]),
+        );
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::grant(named_key, other_account_addr, Role::Writer),
+        );
+        call_shared_contract(
+            &mut builder,
+            other_account_addr,
+            contract_hash,
+            AuthenticatorArgs::add(named_key, &["ID2;VALUE2"]),
+        );
+
+        // make assertions
+        assert_eq!(
+            get_shared_secret_codes(&builder, contract_hash, named_key),
+            vec!["ID1;VALUE1", "ID2;VALUE2"]
+        );
+        assert!(!has_named_key(&builder, account_addr, named_key));
+        assert!(
+            get_contract_named_key(&builder, contract_hash, &format!("{}_acl", named_key))
+                .is_some()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(18) [65554]")]
+    fn should_panic_write_without_role() {
+        let named_key = "team-list";
+        let mut builder = setup("my-named-key");
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+        let other_account_addr = AccountHash::from(&PublicKey::from(
+            &SecretKey::ed25519_from_bytes(OTHER_ACCOUNT).unwrap(),
+        ));
+
+        install_contract(&mut builder, account_addr);
+        let contract_hash = get_contract_hash(&builder, account_addr);
+
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::add(named_key, &["ID1;VALUE1"]),
+        );
+        call_shared_contract(
+            &mut builder,
+            other_account_addr,
+            contract_hash,
+            AuthenticatorArgs::del(named_key, &["ID1"]),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(18) [65554]")]
+    fn should_panic_write_as_reader() {
+        let named_key = "team-list";
+        let mut builder = setup("my-named-key");
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+        let other_account_addr = AccountHash::from(&PublicKey::from(
+            &SecretKey::ed25519_from_bytes(OTHER_ACCOUNT).unwrap(),
+        ));
+
+        install_contract(&mut builder, account_addr);
+        let contract_hash = get_contract_hash(&builder, account_addr);
+
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::add(named_key, &["ID1;VALUE1"]),
+        );
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::grant(named_key, other_account_addr, Role::Reader),
+        );
+        call_shared_contract(
+            &mut builder,
+            other_account_addr,
+            contract_hash,
+            AuthenticatorArgs::get(named_key),
+        );
+        call_shared_contract(
+            &mut builder,
+            other_account_addr,
+            contract_hash,
+            AuthenticatorArgs::add(named_key, &["ID2;VALUE2"]),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(18) [65554]")]
+    fn should_panic_write_after_revoke() {
+        let named_key = "team-list";
+        let mut builder = setup("my-named-key");
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+        let other_account_addr = AccountHash::from(&PublicKey::from(
+            &SecretKey::ed25519_from_bytes(OTHER_ACCOUNT).unwrap(),
+        ));
+
+        install_contract(&mut builder, account_addr);
+        let contract_hash = get_contract_hash(&builder, account_addr);
+
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::add(named_key, &["ID1;VALUE1"]),
+        );
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::grant(named_key, other_account_addr, Role::Writer),
+        );
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::revoke(named_key, other_account_addr),
+        );
+        call_shared_contract(
+            &mut builder,
+            other_account_addr,
+            contract_hash,
+            AuthenticatorArgs::add(named_key, &["ID2;VALUE2"]),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(19) [65555]")]
+    fn should_panic_grant_on_account_list() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+        let other_account_addr = AccountHash::from(&PublicKey::from(
+            &SecretKey::ed25519_from_bytes(OTHER_ACCOUNT).unwrap(),
+        ));
+
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::grant(named_key, other_account_addr, Role::Reader).session_args(),
+        );
+    }
+
+    #[test]
+    fn should_add_and_del_devices() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+        let other_public_key =
+            PublicKey::from(&SecretKey::ed25519_from_bytes(OTHER_ACCOUNT).unwrap());
+
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::add_device(
+                named_key,
+                public_key.clone(),
+                "laptop",
+                DeviceScope::Full,
+            )
+            .session_args(),
+        );
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::add_device(
+                named_key,
+                other_public_key.clone(),
+                "phone",
+                DeviceScope::AddOnly,
+            )
+            .session_args(),
+        );
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::add_device(
+                named_key,
+                other_public_key.clone(),
+                "phone",
+                DeviceScope::ReadOnly,
+            )
+            .session_args(),
+        );
+        // The deploy is signed by the laptop, which can call every method
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE1"],
+            "add",
+        );
+
+        // make assertions
+        assert_eq!(
+            get_devices(&builder, account_addr, named_key),
+            vec![
+                Device::new(public_key.clone(), "laptop", DeviceScope::Full),
+                Device::new(other_public_key.clone(), "phone", DeviceScope::ReadOnly),
+            ]
+        );
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, named_key),
+            vec!["ID1;VALUE1"]
+        );
+
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::del_device(named_key, other_public_key).session_args(),
+        );
+        assert_eq!(
+            get_devices(&builder, account_addr, named_key),
+            vec![Device::new(public_key, "laptop", DeviceScope::Full)]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(20) [65556]")]
+    fn should_panic_add_from_read_only_device() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::add_device(named_key, public_key, "laptop", DeviceScope::ReadOnly)
+                .session_args(),
+        );
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::get(named_key).session_args(),
+        );
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE1"],
+            "add",
+        );
+    }
+
+    #[test]
+    fn should_only_add_new_elements_from_add_only_device() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE1"],
+            "add",
+        );
+        // The other list has no device, its elements can be moved to the list
+        call_contract(
+            &mut builder,
+            account_addr,
+            "other-key",
+            vec!["ID1;OTHER", "ID4;VALUE4"],
+            "add",
+        );
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::add_device(named_key, public_key, "phone", DeviceScope::AddOnly)
+                .session_args(),
+        );
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID2;VALUE2"],
+            "insert",
+        );
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::batch(
+                named_key,
+                &[Operation::Add(SecretEntry::new("ID3", "VALUE3"))],
+            )
+            .session_args(),
+        );
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::batch(
+                "other-key",
+                &[Operation::Move {
+                    id: String::from("ID4"),
+                    named_key: String::from(named_key),
+                }],
+            )
+            .session_args(),
+        );
+
+        // Every call replacing, changing or removing an element of the list is reverted
+        let device_not_allowed = "ApiError::User(20) [65556]";
+        for session_args in vec![
+            AuthenticatorArgs::add(named_key, &["ID1;NEW"]).session_args(),
+            AuthenticatorArgs::update(named_key, &["ID1;NEW"]).session_args(),
+            AuthenticatorArgs::del(named_key, &["ID1"]).session_args(),
+            AuthenticatorArgs::batch(
+                named_key,
+                &[Operation::Upsert(SecretEntry::new("ID1", "NEW"))],
+            )
+            .session_args(),
+        ] {
+            assert!(
+                call_contract_with_args_error(&mut builder, account_addr, session_args)
+                    .contains(device_not_allowed)
+            );
+        }
+        // The move would replace the element of the list having the same ID
+        assert!(call_contract_with_args_error(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::batch(
+                "other-key",
+                &[Operation::Move {
+                    id: String::from("ID1"),
+                    named_key: String::from(named_key),
+                }],
+            )
+            .session_args(),
+        )
+        .contains("ApiError::User(4) [65540]"));
+
+        // make assertions
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, named_key),
+            vec!["ID1;VALUE1", "ID2;VALUE2", "ID3;VALUE3", "ID4;VALUE4"]
+        );
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, "other-key"),
+            vec!["ID1;OTHER"]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(21) [65557]")]
+    fn should_panic_del_unknown_device() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::del_device(named_key, public_key).session_args(),
+        );
+    }
+
+    #[test]
+    fn should_restrict_shared_list_to_devices_of_the_list() {
+        let named_key = "team-list";
+        let mut builder = setup("my-named-key");
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+        let other_public_key =
+            PublicKey::from(&SecretKey::ed25519_from_bytes(OTHER_ACCOUNT).unwrap());
+        let other_account_addr = AccountHash::from(&other_public_key);
+
+        install_contract(&mut builder, account_addr);
+        let contract_hash = get_contract_hash(&builder, account_addr);
+
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::add(named_key, &["ID1;VALUE1"]),
+        );
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::grant(named_key, other_account_addr, Role::Writer),
+        );
+        // The owner chooses the keys of every account allowed to call the list
+        for (device, label, scope) in [
+            (public_key.clone(), "laptop", DeviceScope::Full),
+            (other_public_key.clone(), "teammate", DeviceScope::AddOnly),
+        ] {
+            call_shared_contract(
+                &mut builder,
+                account_addr,
+                contract_hash,
+                AuthenticatorArgs::add_device(named_key, device, label, scope),
+            );
+        }
+        call_shared_contract(
+            &mut builder,
+            other_account_addr,
+            contract_hash,
+            AuthenticatorArgs::insert(named_key, &["ID2;VALUE2"]),
+        );
+
+        // make assertions
+        assert_eq!(
+            get_shared_secret_codes(&builder, contract_hash, named_key),
+            vec!["ID1;VALUE1", "ID2;VALUE2"]
+        );
+        let devices_key =
+            get_contract_named_key(&builder, contract_hash, &format!("{}_devices", named_key))
+                .expect("named key should exist");
+        let devices: Vec<Device> = builder
+            .query(None, devices_key, &[])
+            .expect("should query devices")
+            .as_cl_value()
+            .cloned()
+            .expect("should be CLValue")
+            .into_t()
+            .expect("should be a list of devices");
+        assert_eq!(
+            devices,
+            vec![
+                Device::new(public_key, "laptop", DeviceScope::Full),
+                Device::new(other_public_key, "teammate", DeviceScope::AddOnly),
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(20) [65556]")]
+    fn should_panic_write_from_key_not_in_devices_of_shared_list() {
+        let named_key = "team-list";
+        let mut builder = setup("my-named-key");
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+        let other_account_addr = AccountHash::from(&PublicKey::from(
+            &SecretKey::ed25519_from_bytes(OTHER_ACCOUNT).unwrap(),
+        ));
+
+        install_contract(&mut builder, account_addr);
+        let contract_hash = get_contract_hash(&builder, account_addr);
+
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::add(named_key, &["ID1;VALUE1"]),
+        );
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::grant(named_key, other_account_addr, Role::Writer),
+        );
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::add_device(named_key, public_key, "laptop", DeviceScope::Full),
+        );
+        // The writer has the role, but its key is not a device of the list
+        call_shared_contract(
+            &mut builder,
+            other_account_addr,
+            contract_hash,
+            AuthenticatorArgs::insert(named_key, &["ID2;VALUE2"]),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(18) [65554]")]
+    fn should_panic_add_device_as_writer() {
+        let named_key = "team-list";
+        let mut builder = setup("my-named-key");
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+        let other_public_key =
+            PublicKey::from(&SecretKey::ed25519_from_bytes(OTHER_ACCOUNT).unwrap());
+        let other_account_addr = AccountHash::from(&other_public_key);
+
+        install_contract(&mut builder, account_addr);
+        let contract_hash = get_contract_hash(&builder, account_addr);
+
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::add(named_key, &["ID1;VALUE1"]),
+        );
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::grant(named_key, other_account_addr, Role::Writer),
+        );
+        // Only the owner changes the devices of a shared list
+        call_shared_contract(
+            &mut builder,
+            other_account_addr,
+            contract_hash,
+            AuthenticatorArgs::add_device(named_key, other_public_key, "phone", DeviceScope::Full),
+        );
+    }
+
+    #[test]
+    fn should_recover_shared_list() {
+        let named_key = "team-list";
+        let mut builder = setup("my-named-key");
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+        let other_account_addr = AccountHash::from(&PublicKey::from(
+            &SecretKey::ed25519_from_bytes(OTHER_ACCOUNT).unwrap(),
+        ));
+        let recovery_account_addr = AccountHash::from(&PublicKey::from(
+            &SecretKey::ed25519_from_bytes(RECOVERY_ACCOUNT).unwrap(),
+        ));
+
+        install_contract(&mut builder, account_addr);
+        let contract_hash = get_contract_hash(&builder, account_addr);
+
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::add(named_key, &["ID1;VALUE1"]),
+        );
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::set_guardians(named_key, &[other_account_addr], 1, 1_000),
+        );
+        call_shared_contract_at(
+            &mut builder,
+            other_account_addr,
+            contract_hash,
+            AuthenticatorArgs::approve_recovery(named_key, recovery_account_addr),
+            5_000,
+        );
+        call_shared_contract_at(
+            &mut builder,
+            other_account_addr,
+            contract_hash,
+            AuthenticatorArgs::complete_recovery(named_key),
+            6_000,
+        );
+        call_shared_contract(
+            &mut builder,
+            recovery_account_addr,
+            contract_hash,
+            AuthenticatorArgs::add(named_key, &["ID2;VALUE2"]),
+        );
+
+        // make assertions
+        assert_eq!(
+            get_shared_secret_codes(&builder, contract_hash, named_key),
+            vec!["ID1;VALUE1", "ID2;VALUE2"]
+        );
+        assert!(get_contract_named_key(
+            &builder,
+            contract_hash,
+            &format!("{}_recovery", named_key)
+        )
+        .is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(25) [65561]")]
+    fn should_panic_complete_recovery_before_timelock() {
+        let named_key = "team-list";
+        let mut builder = setup("my-named-key");
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+        let other_account_addr = AccountHash::from(&PublicKey::from(
+            &SecretKey::ed25519_from_bytes(OTHER_ACCOUNT).unwrap(),
+        ));
+        let recovery_account_addr = AccountHash::from(&PublicKey::from(
+            &SecretKey::ed25519_from_bytes(RECOVERY_ACCOUNT).unwrap(),
+        ));
+
+        install_contract(&mut builder, account_addr);
+        let contract_hash = get_contract_hash(&builder, account_addr);
+
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::add(named_key, &["ID1;VALUE1"]),
+        );
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::set_guardians(named_key, &[other_account_addr], 1, 1_000),
+        );
+        call_shared_contract_at(
+            &mut builder,
+            other_account_addr,
+            contract_hash,
+            AuthenticatorArgs::approve_recovery(named_key, recovery_account_addr),
+            5_000,
+        );
+        call_shared_contract_at(
+            &mut builder,
+            other_account_addr,
+            contract_hash,
+            AuthenticatorArgs::complete_recovery(named_key),
+            5_500,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(22) [65558]")]
+    fn should_panic_approve_recovery_as_non_guardian() {
+        let named_key = "team-list";
+        let mut builder = setup("my-named-key");
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+        let other_account_addr = AccountHash::from(&PublicKey::from(
+            &SecretKey::ed25519_from_bytes(OTHER_ACCOUNT).unwrap(),
+        ));
+        let recovery_account_addr = AccountHash::from(&PublicKey::from(
+            &SecretKey::ed25519_from_bytes(RECOVERY_ACCOUNT).unwrap(),
+        ));
+
+        install_contract(&mut builder, account_addr);
+        let contract_hash = get_contract_hash(&builder, account_addr);
+
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::add(named_key, &["ID1;VALUE1"]),
+        );
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::set_guardians(named_key, &[other_account_addr], 1, 1_000),
+        );
+        call_shared_contract(
+            &mut builder,
+            recovery_account_addr,
+            contract_hash,
+            AuthenticatorArgs::approve_recovery(named_key, recovery_account_addr),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(18) [65554]")]
+    fn should_panic_previous_owner_after_recovery() {
+        let named_key = "team-list";
+        let mut builder = setup("my-named-key");
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+        let other_account_addr = AccountHash::from(&PublicKey::from(
+            &SecretKey::ed25519_from_bytes(OTHER_ACCOUNT).unwrap(),
+        ));
+        let recovery_account_addr = AccountHash::from(&PublicKey::from(
+            &SecretKey::ed25519_from_bytes(RECOVERY_ACCOUNT).unwrap(),
+        ));
+
+        install_contract(&mut builder, account_addr);
+        let contract_hash = get_contract_hash(&builder, account_addr);
+
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::add(named_key, &["ID1;VALUE1"]),
+        );
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::set_guardians(named_key, &[other_account_addr], 1, 1_000),
+        );
+        call_shared_contract_at(
+            &mut builder,
+            other_account_addr,
+            contract_hash,
+            AuthenticatorArgs::approve_recovery(named_key, recovery_account_addr),
+            5_000,
+        );
+        call_shared_contract_at(
+            &mut builder,
+            other_account_addr,
+            contract_hash,
+            AuthenticatorArgs::complete_recovery(named_key),
+            6_000,
+        );
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::add(named_key, &["ID2;VALUE2"]),
+        );
+    }
+
+    #[test]
+    fn should_claim_shared_list_as_reader() {
+        let named_key = "team-list";
+        let mut builder = setup("my-named-key");
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+        let other_account_addr = AccountHash::from(&PublicKey::from(
+            &SecretKey::ed25519_from_bytes(OTHER_ACCOUNT).unwrap(),
+        ));
+
+        install_contract(&mut builder, account_addr);
+        let contract_hash = get_contract_hash(&builder, account_addr);
+
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::add(named_key, &["ID1;VALUE1"]),
+        );
+        call_shared_contract_at(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::set_beneficiary(named_key, other_account_addr, Role::Reader, 1_000),
+            1_000,
+        );
+        call_shared_contract_at(
+            &mut builder,
+            other_account_addr,
+            contract_hash,
+            AuthenticatorArgs::claim(named_key),
+            3_000,
+        );
+        call_shared_contract(
+            &mut builder,
+            other_account_addr,
+            contract_hash,
+            AuthenticatorArgs::get(named_key),
+        );
+
+        // make assertions
+        assert!(get_contract_named_key(
+            &builder,
+            contract_hash,
+            &format!("{}_inheritance", named_key)
+        )
+        .is_none());
+        assert_eq!(
+            get_shared_secret_codes(&builder, contract_hash, named_key),
+            vec!["ID1;VALUE1"]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(27) [65563]")]
+    fn should_panic_claim_after_heartbeat() {
+        let named_key = "team-list";
+        let mut builder = setup("my-named-key");
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+        let other_account_addr = AccountHash::from(&PublicKey::from(
+            &SecretKey::ed25519_from_bytes(OTHER_ACCOUNT).unwrap(),
+        ));
+
+        install_contract(&mut builder, account_addr);
+        let contract_hash = get_contract_hash(&builder, account_addr);
+
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::add(named_key, &["ID1;VALUE1"]),
+        );
+        call_shared_contract_at(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::set_beneficiary(named_key, other_account_addr, Role::Reader, 1_000),
+            1_000,
+        );
+        call_shared_contract_at(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::heartbeat(named_key),
+            1_500,
+        );
+        call_shared_contract_at(
+            &mut builder,
+            other_account_addr,
+            contract_hash,
+            AuthenticatorArgs::claim(named_key),
+            2_200,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(26) [65562]")]
+    fn should_panic_claim_as_non_beneficiary() {
+        let named_key = "team-list";
+        let mut builder = setup("my-named-key");
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+        let other_account_addr = AccountHash::from(&PublicKey::from(
+            &SecretKey::ed25519_from_bytes(OTHER_ACCOUNT).unwrap(),
+        ));
+
+        install_contract(&mut builder, account_addr);
+        let contract_hash = get_contract_hash(&builder, account_addr);
+
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::add(named_key, &["ID1;VALUE1"]),
+        );
+        call_shared_contract_at(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::set_beneficiary(named_key, other_account_addr, Role::Reader, 1_000),
+            1_000,
+        );
+        call_shared_contract_at(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::claim(named_key),
+            3_000,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(18) [65554]")]
+    fn should_panic_previous_owner_after_claim() {
+        let named_key = "team-list";
+        let mut builder = setup("my-named-key");
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+        let other_account_addr = AccountHash::from(&PublicKey::from(
+            &SecretKey::ed25519_from_bytes(OTHER_ACCOUNT).unwrap(),
+        ));
+
+        install_contract(&mut builder, account_addr);
+        let contract_hash = get_contract_hash(&builder, account_addr);
+
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::add(named_key, &["ID1;VALUE1"]),
+        );
+        call_shared_contract_at(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::set_beneficiary(named_key, other_account_addr, Role::Owner, 1_000),
+            1_000,
+        );
+        call_shared_contract_at(
+            &mut builder,
+            other_account_addr,
+            contract_hash,
+            AuthenticatorArgs::claim(named_key),
+            3_000,
+        );
+        call_shared_contract(
+            &mut builder,
+            other_account_addr,
+            contract_hash,
+            AuthenticatorArgs::add(named_key, &["ID2;VALUE2"]),
+        );
+        call_shared_contract(
+            &mut builder,
+            account_addr,
+            contract_hash,
+            AuthenticatorArgs::add(named_key, &["ID3;VALUE3"]),
+        );
+    }
+
+    #[test]
+    fn should_keep_order_of_elements() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE1", "ID2;VALUE2", "ID3;VALUE3", "ID4;VALUE4"],
+            "add",
+        );
+        call_contract(&mut builder, account_addr, named_key, vec!["ID2"], "del");
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;NEW"],
+            "update",
+        );
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::batch(
+                named_key,
+                &[Operation::RenameId {
+                    id: String::from("ID3"),
+                    new_id: String::from("ID5"),
+                    secret: None,
+                }],
+            )
+            .session_args(),
+        );
+
+        // make assertions
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, named_key),
+            vec!["ID1;NEW", "ID5;VALUE3", "ID4;VALUE4"],
+            "The elements should keep their positions"
+        );
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1", "ID4"],
+            "del",
+        );
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID6;VALUE6"],
+            "add",
+        );
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, named_key),
+            vec!["ID5;VALUE3", "ID6;VALUE6"],
+            "The first and the last elements should be unlinked"
+        );
+    }
+
+    #[test]
+    fn should_reorder_and_pin_elements() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE1", "ID2;VALUE2", "ID3;VALUE3", "ID4;VALUE4"],
+            "add",
+        );
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::reorder(named_key, &["ID4", "ID2", "ID3", "ID1"]).session_args(),
+        );
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::pin(named_key, "ID3", true).session_args(),
+        );
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID3;NEW"],
+            "update",
+        );
+
+        let entries = get_named_key(&builder, account_addr, named_key)
+            .into_uref()
+            .expect("should be uref");
+
+        // make assertions
+        assert_eq!(
+            get_secret_codes(&builder, account_addr, named_key),
+            vec!["ID4;VALUE4", "ID2;VALUE2", "ID3;NEW", "ID1;VALUE1"]
+        );
+        assert!(
+            get_dictionary_item::<Option<SecretEntry>>(&builder, entries, "ID3")
+                .flatten()
+                .expect("element should exist")
+                .pinned,
+            "The update should keep the pin"
+        );
+        assert_eq!(
+            get_last_events(&builder),
+            vec![event("updated", named_key, Some("ID3"), 4)]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ApiError::User(29) [65565]")]
+    fn should_panic_reorder_without_all_ids() {
+        let named_key = "my-named-key";
+        let mut builder = setup(named_key);
+        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        // Create an AccountHash from a public key.
+        let account_addr = AccountHash::from(&public_key);
+
+        call_contract(
+            &mut builder,
+            account_addr,
+            named_key,
+            vec!["ID1;VALUE1", "ID2;VALUE2", "ID3;VALUE3", "ID4;VALUE4"],
+            "add",
+        );
+        call_contract_with_args(
+            &mut builder,
+            account_addr,
+            AuthenticatorArgs::reorder(named_key, &["ID4", "ID3", "ID2"]).session_args(),
         );
     }
 }